@@ -0,0 +1,142 @@
+use std::collections::HashMap;
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::Data;
+use syn::DeriveInput;
+use syn::Fields;
+use syn::Ident;
+
+use crate::util::is_boxed_self;
+
+/// Returns the construction of `path` from arbitrary `fields`, where the
+/// location field of `location` variants is the caller's location.
+fn construct(
+	path: TokenStream2,
+	fields: &Fields,
+	location: bool,
+	arbitrary: &TokenStream2,
+) -> TokenStream2 {
+	let value = quote!(#arbitrary::Arbitrary::arbitrary(u)?);
+
+	match fields {
+		Fields::Named(fields) => {
+			let idents = fields.named.iter().map(|field| &field.ident);
+			quote!(#path { #(#idents: #value),* })
+		},
+		Fields::Unnamed(fields) => {
+			let values = (0..fields.unnamed.len()).map(|i| {
+				if location && i == 1 {
+					quote!(::core::panic::Location::caller())
+				} else {
+					value.clone()
+				}
+			});
+			quote!(#path( #(#values),* ))
+		},
+		Fields::Unit => path,
+	}
+}
+
+/// Generates the impl of `arbitrary::Arbitrary` for the target type, choosing
+/// any variant except the `infallible` one with arbitrary arguments.
+///
+/// Variants holding a `Box<Self>` come last, thus exhausted input, which makes
+/// `arbitrary` choose the first one, ends the recursion.
+pub fn generate(
+	input: &DeriveInput,
+	location_variants: &[Ident],
+	infallible: Option<&Ident>,
+	variant_cfgs: &HashMap<Ident, TokenStream2>,
+	krate: &TokenStream2,
+) -> syn::Result<TokenStream2> {
+	let ident = &input.ident;
+	let arbitrary = quote!(#krate::__private::arbitrary);
+	let recursive = |fields: &Fields| fields.iter().any(|field| is_boxed_self(&field.ty, ident));
+
+	let mut field_types = Vec::new();
+	let body = {
+		match &input.data {
+			Data::Struct(data) => {
+				field_types.extend(data.fields.iter().map(|field| &field.ty));
+				let construct = construct(quote!(Self), &data.fields, false, &arbitrary);
+				quote!(::core::result::Result::Ok(#construct))
+			},
+			Data::Enum(data) => {
+				let mut variants: Vec<_> = data
+					.variants
+					.iter()
+					.filter(|variant| Some(&variant.ident) != infallible)
+					.collect();
+				if variants.is_empty() {
+					return Err(syn::Error::new_spanned(
+						ident,
+						"'arbitrary' requires at least one inhabited variant!",
+					));
+				}
+				variants.sort_by_key(|variant| recursive(&variant.fields));
+
+				let mut constructors = Vec::new();
+				for variant in variants {
+					let variant_ident = &variant.ident;
+					let location = location_variants.contains(variant_ident);
+					let cfg = variant_cfgs.get(variant_ident).cloned().unwrap_or_default();
+
+					field_types.extend(
+						variant
+							.fields
+							.iter()
+							.enumerate()
+							.filter(|(i, _)| !(location && *i == 1))
+							.map(|(_, field)| &field.ty),
+					);
+
+					let construct = construct(
+						quote!(Self::#variant_ident),
+						&variant.fields,
+						location,
+						&arbitrary,
+					);
+					constructors.push(quote! {
+						#cfg
+						|u| ::core::result::Result::Ok(#construct),
+					});
+				}
+
+				quote! {
+					let constructors: &[fn(&mut #arbitrary::Unstructured<'arbitrary>) -> #arbitrary::Result<Self>] = &[
+						#(#constructors)*
+					];
+					(u.choose(constructors)?)(u)
+				}
+			},
+			Data::Union(_) => unreachable!("unions are rejected before"),
+		}
+	};
+
+	// Generic arguments are required to be arbitrary, which the recursive
+	// arguments are by the impl itself.
+	let mut generics = input.generics.clone();
+	generics.params.insert(0, syn::parse_quote!('arbitrary));
+	if input.generics.type_params().next().is_some() {
+		let where_clause = generics.make_where_clause();
+		for ty in field_types {
+			if !is_boxed_self(ty, ident) {
+				where_clause
+					.predicates
+					.push(syn::parse_quote!(#ty: #arbitrary::Arbitrary<'arbitrary>));
+			}
+		}
+	}
+	let (generics_impl, _, generics_where) = generics.split_for_impl();
+	let (_, generics_type, _) = input.generics.split_for_impl();
+
+	Ok(quote! {
+		#[automatically_derived]
+		impl #generics_impl #arbitrary::Arbitrary<'arbitrary> for #ident #generics_type #generics_where {
+			fn arbitrary(u: &mut #arbitrary::Unstructured<'arbitrary>) -> #arbitrary::Result<Self> {
+				#body
+			}
+		}
+	})
+}
@@ -0,0 +1,219 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::parse::Parser;
+use syn::parse_quote;
+use syn::punctuated::Punctuated;
+use syn::Data;
+use syn::DeriveInput;
+use syn::Fields;
+use syn::Token;
+
+use crate::util::error;
+use crate::util::AttrArg;
+use crate::util::AttrValue;
+
+/// Name of the helper attribute, which is removed from the rewritten item.
+const HELPER: &str = "compound_error";
+
+/// Expands the `#[compound_error(...)]` attribute macro given its `args` and
+/// the annotated `input` item.
+pub fn expand(args: TokenStream2, input: DeriveInput) -> TokenStream {
+	let parser = Punctuated::<AttrArg, Token![,]>::parse_terminated;
+	let args = {
+		match parser.parse2(args) {
+			Err(err) => return err.to_compile_error().into(),
+			Ok(ok) => ok,
+		}
+	};
+
+	let mut skip_debug = false;
+	let mut context = false;
+	let mut forwarded_args: Punctuated<AttrArg, Token![,]> = Punctuated::new();
+
+	for arg in args {
+		if arg.path.is_ident("skip_debug") {
+			if !matches!(arg.value, AttrValue::Flag) {
+				return error(&arg.path, "'skip_debug' attribute takes no arguments!");
+			}
+			skip_debug = true;
+		} else if arg.path.is_ident("context") {
+			if !matches!(arg.value, AttrValue::Flag) {
+				return error(&arg.path, "'context' attribute takes no arguments!");
+			}
+			context = true;
+		} else {
+			forwarded_args.push(arg);
+		}
+	}
+
+	let mut input = input;
+	if let Err(err) = inject_location_fields(&mut input) {
+		return err.to_compile_error().into();
+	}
+	if context {
+		if let Err(err) = inject_context_variant(&mut input) {
+			return err.to_compile_error().into();
+		}
+	}
+	// The derive checks that conditional variants are removed by the compiler
+	if let Err(err) = apply_cfgs(&mut input) {
+		return err.to_compile_error().into();
+	}
+
+	// The input as seen by the derive, i.e. with the item-level arguments
+	// turned into a helper attribute.
+	let mut derive_input = input.clone();
+	if !forwarded_args.is_empty() {
+		derive_input
+			.attrs
+			.push(parse_quote!( #[compound_error( #forwarded_args )] ));
+	}
+
+	let generated: TokenStream2 = crate::expand(derive_input).into();
+
+	let mut item = input;
+	strip_helper_attrs(&mut item);
+
+	if !skip_debug && !derives_debug(&item.attrs) {
+		item.attrs
+			.push(parse_quote!( #[derive(::core::fmt::Debug)] ));
+	}
+
+	let output = quote! {
+		#item
+		#generated
+	};
+
+	output.into()
+}
+
+/// Adds the location field to all variants marked as `location`, which do
+/// not already specify it.
+fn inject_location_fields(item: &mut DeriveInput) -> syn::Result<()> {
+	if let Data::Enum(data) = &mut item.data {
+		for variant in &mut data.variants {
+			if !has_helper_arg(&variant.attrs, "location")? {
+				continue;
+			}
+
+			if let Fields::Unnamed(fields) = &mut variant.fields {
+				if fields.unnamed.len() == 1 {
+					fields.unnamed.push(syn::Field {
+						attrs: Vec::new(),
+						vis: syn::Visibility::Inherited,
+						ident: None,
+						colon_token: None,
+						ty: parse_quote!(&'static ::core::panic::Location<'static>),
+					});
+				}
+			}
+		}
+	}
+
+	Ok(())
+}
+
+/// Adds the variant `Context(String, Box<Self>)` marked as `context`.
+fn inject_context_variant(item: &mut DeriveInput) -> syn::Result<()> {
+	let ident = &item.ident;
+	let (_, generics_type, _) = item.generics.split_for_impl();
+
+	match &mut item.data {
+		Data::Enum(data) => {
+			data.variants.push(parse_quote! {
+				/// Another error of this type along with a message giving context.
+				#[compound_error( context )]
+				Context(
+					::std::string::String,
+					::std::boxed::Box< #ident #generics_type >
+				)
+			});
+			Ok(())
+		},
+		_ => {
+			Err(syn::Error::new_spanned(
+				ident,
+				"'context' can only be used on enums!",
+			))
+		},
+	}
+}
+
+/// Checks whether any of the helper attributes within `attrs` contains the
+/// argument `key`.
+fn has_helper_arg(attrs: &[syn::Attribute], key: &str) -> syn::Result<bool> {
+	for attr in attrs {
+		if !attr.path.is_ident(HELPER) || attr.tokens.is_empty() {
+			continue;
+		}
+
+		let parser = Punctuated::<AttrArg, Token![,]>::parse_terminated;
+		if attr
+			.parse_args_with(parser)?
+			.iter()
+			.any(|arg| arg.path.is_ident(key))
+		{
+			return Ok(true);
+		}
+	}
+
+	Ok(false)
+}
+
+/// Turns the `cfg(...)` helper arguments of variants into `#[cfg(...)]`
+/// attributes of the respective variants.
+fn apply_cfgs(item: &mut DeriveInput) -> syn::Result<()> {
+	if let Data::Enum(data) = &mut item.data {
+		for variant in &mut data.variants {
+			let mut cfgs = Vec::new();
+
+			for attr in &variant.attrs {
+				if !attr.path.is_ident(HELPER) || attr.tokens.is_empty() {
+					continue;
+				}
+
+				let parser = Punctuated::<AttrArg, Token![,]>::parse_terminated;
+				for arg in attr.parse_args_with(parser)? {
+					match &arg.value {
+						AttrValue::List(predicate) if arg.path.is_ident("cfg") => {
+							cfgs.push(parse_quote!( #[cfg( #predicate )] ));
+						},
+						_ => {},
+					}
+				}
+			}
+
+			variant.attrs.extend(cfgs);
+		}
+	}
+
+	Ok(())
+}
+
+/// Removes all helper attributes from the item and its variants.
+fn strip_helper_attrs(item: &mut DeriveInput) {
+	item.attrs.retain(|attr| !attr.path.is_ident(HELPER));
+
+	if let Data::Enum(data) = &mut item.data {
+		for variant in &mut data.variants {
+			variant.attrs.retain(|attr| !attr.path.is_ident(HELPER));
+		}
+	}
+}
+
+/// Checks whether `Debug` is already derived via the given attributes.
+fn derives_debug(attrs: &[syn::Attribute]) -> bool {
+	let parser = Punctuated::<syn::Path, Token![,]>::parse_terminated;
+
+	attrs
+		.iter()
+		.filter(|attr| attr.path.is_ident("derive"))
+		.filter_map(|attr| attr.parse_args_with(parser).ok())
+		.flatten()
+		.any(|path| {
+			path.segments
+				.last()
+				.is_some_and(|segment| segment.ident == "Debug")
+		})
+}
@@ -0,0 +1,154 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::braced;
+use syn::parenthesized;
+use syn::parse::Parse;
+use syn::parse::ParseStream;
+use syn::punctuated::Punctuated;
+use syn::DeriveInput;
+use syn::Token;
+
+use crate::util::AttrArg;
+
+/// A compound error enum as written in `compound_errors! { ... }`.
+struct Definition {
+	attrs: Vec<syn::Attribute>,
+	vis: syn::Visibility,
+	ident: syn::Ident,
+	generics: syn::Generics,
+	variants: Punctuated<VariantDefinition, Token![,]>,
+}
+
+/// A single variant of a [`Definition`], e.g. `Parse(ParseError) no_source`.
+struct VariantDefinition {
+	attrs: Vec<syn::Attribute>,
+	ident: syn::Ident,
+	ty: syn::Type,
+	args: Vec<AttrArg>,
+}
+
+impl Parse for Definition {
+	fn parse(input: ParseStream) -> syn::Result<Self> {
+		let attrs = input.call(syn::Attribute::parse_outer)?;
+		let vis = input.parse()?;
+		input.parse::<Token![enum]>()?;
+		let ident = input.parse()?;
+		let mut generics: syn::Generics = input.parse()?;
+		generics.where_clause = input.parse()?;
+
+		let content;
+		braced!(content in input);
+		let variants = content.parse_terminated(VariantDefinition::parse)?;
+
+		Ok(Self {
+			attrs,
+			vis,
+			ident,
+			generics,
+			variants,
+		})
+	}
+}
+
+impl Parse for VariantDefinition {
+	fn parse(input: ParseStream) -> syn::Result<Self> {
+		let attrs = input.call(syn::Attribute::parse_outer)?;
+		let ident = input.parse()?;
+
+		let content;
+		parenthesized!(content in input);
+		let ty = content.parse()?;
+
+		let mut args = Vec::new();
+		while !input.is_empty() && !input.peek(Token![,]) {
+			args.push(input.parse()?);
+		}
+
+		Ok(Self {
+			attrs,
+			ident,
+			ty,
+			args,
+		})
+	}
+}
+
+impl Definition {
+	/// Turns this definition into a regular enum carrying helper attributes.
+	fn into_derive_input(self) -> syn::Result<DeriveInput> {
+		let Self {
+			attrs,
+			vis,
+			ident,
+			generics,
+			variants,
+		} = self;
+		let where_clause = &generics.where_clause;
+
+		let variants = variants.into_iter().map(|variant| {
+			let VariantDefinition {
+				attrs,
+				ident,
+				ty,
+				args,
+			} = variant;
+
+			let helper = {
+				if args.is_empty() {
+					TokenStream2::new()
+				} else {
+					quote!( #[compound_error( #(#args),* )] )
+				}
+			};
+
+			quote! {
+				#(#attrs)*
+				#helper
+				#ident ( #ty )
+			}
+		});
+
+		syn::parse2(quote! {
+			#(#attrs)*
+			#vis enum #ident #generics #where_clause {
+				#(#variants),*
+			}
+		})
+	}
+}
+
+/// Expands the `compound_errors! { ... }` macro.
+pub fn expand(input: TokenStream2) -> TokenStream {
+	let parser = |input: ParseStream| {
+		let mut definitions = Vec::new();
+		while !input.is_empty() {
+			definitions.push(input.parse::<Definition>()?);
+		}
+		Ok(definitions)
+	};
+
+	let definitions = {
+		match syn::parse::Parser::parse2(parser, input) {
+			Err(err) => return err.to_compile_error().into(),
+			Ok(ok) => ok,
+		}
+	};
+
+	let mut generated = TokenStream2::new();
+
+	for definition in definitions {
+		let derive_input = {
+			match definition.into_derive_input() {
+				Err(err) => return err.to_compile_error().into(),
+				Ok(ok) => ok,
+			}
+		};
+
+		let expanded: TokenStream2 =
+			crate::attribute::expand(TokenStream2::new(), derive_input).into();
+		generated.extend(expanded);
+	}
+
+	generated.into()
+}
@@ -0,0 +1,3325 @@
+extern crate proc_macro;
+
+mod arbitrary;
+mod attribute;
+mod definition;
+mod map;
+mod merge;
+mod subset;
+mod util;
+
+use std::collections::HashMap;
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::format_ident;
+use quote::quote;
+use quote::quote_spanned;
+use syn::parse::Parse;
+use syn::parse::ParseStream;
+use syn::parse_macro_input;
+use syn::spanned::Spanned;
+use syn::Data;
+use syn::DeriveInput;
+use syn::Fields;
+use syn::Ident;
+use syn::Type;
+use util::attr_args;
+use util::error;
+use util::flag;
+use util::is_boxed_self;
+use util::is_generic_param;
+use util::is_io_error;
+use util::option_inner;
+use util::to_camel_case;
+use util::to_snake_case;
+use util::AttrArg;
+use util::AttrValue;
+
+macro_rules! try_compile {
+	($what:expr, | $err:ident | $ret:expr) => {{
+		match $what {
+			Err($err) => return $ret,
+			Ok(ok) => ok,
+		}
+	}};
+}
+
+macro_rules! flag {
+	($args:expr, $arg:expr) => {
+		try_compile!(flag($args, $arg), |path| {
+			error(path, &format!("'{}' attribute takes no arguments!", $arg))
+		})
+	};
+}
+
+/// A type given to `inline_from`, either directly as in
+/// `inline_from(Foo<T>)` or as string literal as in `inline_from("Foo<T>")`.
+struct InlineFrom {
+	ty: syn::TypePath,
+	span: Span,
+}
+
+impl Parse for InlineFrom {
+	fn parse(input: ParseStream) -> syn::Result<Self> {
+		let (mut ty, span): (syn::TypePath, _) = {
+			if input.peek(syn::LitStr) {
+				let lit: syn::LitStr = input.parse()?;
+				(lit.parse()?, lit.span())
+			} else {
+				let ty: syn::TypePath = input.parse()?;
+				let span = ty.span();
+				(ty, span)
+			}
+		};
+
+		// Drop any turbofish, so that `Foo::<T>` and `Foo<T>` are the same type
+		for segment in &mut ty.path.segments {
+			if let syn::PathArguments::AngleBracketed(args) = &mut segment.arguments {
+				args.colon2_token = None;
+			}
+		}
+
+		Ok(Self {
+			ty,
+			span,
+		})
+	}
+}
+
+/// An argument of `inline_from`, i.e. either a type to inline or the handling
+/// of all variants of the types, which are not inlined explicitly.
+enum InlineFromArg {
+	Type(InlineFrom),
+	Rest(InlineRest),
+}
+
+/// The handling of the variants of an inlined type, which are not inlined
+/// explicitly.
+#[derive(Clone)]
+enum InlineRest {
+	/// Given as `with = <fn>`, mapping these variants to the target type
+	With(Box<syn::Expr>),
+	/// Given as `fallback = <Variant>`, converting these variants into the
+	/// argument of the given variant, or formatting them via `Display` for
+	/// `message` variants
+	Fallback(Ident),
+}
+
+impl Parse for InlineFromArg {
+	fn parse(input: ParseStream) -> syn::Result<Self> {
+		if input.peek(Ident) && input.peek2(syn::Token![=]) {
+			let key: Ident = input.parse()?;
+			input.parse::<syn::Token![=]>()?;
+
+			if key == "with" {
+				Ok(Self::Rest(InlineRest::With(input.parse()?)))
+			} else if key == "fallback" {
+				Ok(Self::Rest(InlineRest::Fallback(input.parse()?)))
+			} else {
+				Err(syn::Error::new_spanned(
+					key,
+					"Unknown argument of 'inline_from', expected 'with' or 'fallback'!",
+				))
+			}
+		} else {
+			Ok(Self::Type(input.parse()?))
+		}
+	}
+}
+
+/// An arm of `from_io`, as in `NotFound => Missing`, where `_` matches all
+/// remaining kinds.
+struct FromIoArm {
+	/// The variant of `std::io::ErrorKind`, `None` for `_`
+	kind: Option<Ident>,
+	variant: Ident,
+}
+
+impl Parse for FromIoArm {
+	fn parse(input: ParseStream) -> syn::Result<Self> {
+		let kind = {
+			if input.peek(syn::Token![_]) {
+				input.parse::<syn::Token![_]>()?;
+				None
+			} else {
+				Some(input.parse()?)
+			}
+		};
+		input.parse::<syn::Token![=>]>()?;
+		let variant = input.parse()?;
+
+		Ok(Self {
+			kind,
+			variant,
+		})
+	}
+}
+
+/// Returns the path of the enum `ty` without any generic arguments, as used in
+/// patterns.
+fn enum_path(ty: &syn::TypePath) -> syn::Path {
+	let mut path = ty.path.clone();
+	path.segments.last_mut().unwrap().arguments = syn::PathArguments::None;
+	path
+}
+
+/// Returns the integer type given by `#[repr(..)]` along with the explicit
+/// discriminants of all variants, as in `= 1`, if the target type is such an
+/// enum and specifies any discriminant explicitly.
+///
+/// Implicit discriminants are left to the compiler, as they depend on the
+/// preceding variants, which might be removed by their configuration.
+fn discriminants(input: &DeriveInput) -> Option<(Ident, Vec<(Ident, proc_macro2::TokenStream)>)> {
+	const INTEGERS: &[&str] = &[
+		"u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+	];
+
+	let data = {
+		match &input.data {
+			Data::Enum(data) => data,
+			_ => return None,
+		}
+	};
+
+	let repr = input
+		.attrs
+		.iter()
+		.filter(|attr| attr.path.is_ident("repr"))
+		.filter_map(|attr| attr.parse_meta().ok())
+		.find_map(|meta| {
+			match meta {
+				syn::Meta::List(list) => {
+					list.nested.into_iter().find_map(|nested| {
+						match nested {
+							syn::NestedMeta::Meta(syn::Meta::Path(path)) => {
+								path.get_ident()
+									.filter(|ident| INTEGERS.iter().any(|int| *ident == int))
+									.cloned()
+							},
+							_ => None,
+						}
+					})
+				},
+				_ => None,
+			}
+		})?;
+
+	if data
+		.variants
+		.iter()
+		.all(|variant| variant.discriminant.is_none())
+	{
+		return None;
+	}
+
+	let discriminants = data
+		.variants
+		.iter()
+		.map(|variant| {
+			let discriminant = {
+				match &variant.discriminant {
+					Some((_, expr)) => quote!(= #expr),
+					None => proc_macro2::TokenStream::new(),
+				}
+			};
+			(variant.ident.clone(), discriminant)
+		})
+		.collect();
+
+	Some((repr, discriminants))
+}
+
+/// Returns the doc comment given by `attrs`, if any.
+fn doc_comment(attrs: &[syn::Attribute]) -> Option<String> {
+	let lines: Vec<_> = attrs
+		.iter()
+		.filter(|attr| attr.path.is_ident("doc"))
+		.filter_map(|attr| {
+			match attr.parse_meta() {
+				Ok(syn::Meta::NameValue(syn::MetaNameValue {
+					lit: syn::Lit::Str(lit),
+					..
+				})) => Some(lit.value()),
+				_ => None,
+			}
+		})
+		.map(|line| line.strip_prefix(' ').map(str::to_string).unwrap_or(line))
+		.collect();
+
+	if lines.is_empty() {
+		None
+	} else {
+		Some(lines.join("\n"))
+	}
+}
+
+/// Parses the level of the `severity` attribute `attr` into the respective
+/// variant of `Severity`.
+fn parse_severity(
+	attr: &AttrArg,
+	krate: &proc_macro2::TokenStream,
+) -> Result<proc_macro2::TokenStream, TokenStream> {
+	let level = {
+		match attr.parse_value::<syn::LitStr>() {
+			Ok(lit) => lit.value(),
+			Err(_) => {
+				return Err(error(
+					&attr.path,
+					"'severity' takes exactly one string argument!",
+				))
+			},
+		}
+	};
+
+	let variant = {
+		match level.as_str() {
+			"info" => quote!(Info),
+			"warning" => quote!(Warning),
+			"error" => quote!(Error),
+			"critical" => quote!(Critical),
+			_ => {
+				return Err(error(
+					&attr.path,
+					"'severity' must be one of \"info\", \"warning\", \"error\" or \"critical\"!",
+				))
+			},
+		}
+	};
+
+	Ok(quote!(#krate::Severity::#variant))
+}
+
+/// Implement `CompoundError` functionality for the target type.
+///
+/// If the target is an enum, `From` is implemented for each variant.
+/// Additionally, variants can be annotated with
+/// `#[compound_error( inline_from(X) )]`
+/// to specify that an "inlining from `X`" should be implemented. In addition
+/// to the `From` impls, by default also `std::error::Error` and
+/// `std::fmt::Display` are implemented for the target type. If the target type
+/// is a struct, no `From` impls, but only `std::error::Error` and
+/// `std::fmt::Display` impls are generated. In any case, the
+/// `compound_error::CompoundError` trait is implemented, giving access to the
+/// title, description, variant name, code, severity, category, message key and
+/// documentation URL of the error.
+///
+/// The generation of the `Error` and `Display` impls can by suppressed by
+/// specifying `#[compound_error( skip_error )]` and
+/// `#[compound_error( skip_display )]` on the target type.
+///
+/// If the target type is an enum, all variants must take exactly one argument.
+/// By default, this argument must implement `std::error::Error`. This can be
+/// circumvented by either specifying the `skip_error` attribute on the target
+/// type or by specifying the `no_source` attribute on the respective variant.
+/// `no_source` causes `None` to be returned by the implementation of
+/// `std::error::Error::source()` on the target type for the respective enum
+/// variant.
+///
+/// # Attributes
+///
+/// Attributes are specified in the following form:
+///
+/// ```text
+/// #[compound_error( attr1, attr2, attr3, ... )]
+/// #[compound_error( attr4, attr5, ... )]
+/// <ELEMENT>
+/// ```
+///
+/// `<ELEMENT>` can be the target type or an enum variant. The following
+/// attributes are available:
+///
+/// On the target type:
+/// * `title = "<title>"`: Set the title of this error to `"<title>"`. This is
+///   relevant for the automatic `Display` implementation on the target type.
+/// * `description = "<description>"`: Set the description of this error to
+///   `"<description>"`. This is relevant for the automatic `Display`
+///   implementation on the target type.
+/// * `skip_display`: Skip the automatic implementation of `std::fmt::Display`
+///   on the target type.
+/// * `skip_error`: Skip the automatic implementation of `std::error::Error` on
+///   the target type.
+/// * `transparent`: forward the source and Display methods through to all
+///   underlying errors without adding an additional message. On structs, this
+///   requires exactly one unnamed field, the wrapped error.
+/// * `chain_depth = <N>`: Additionally show up to `<N>` levels of the sources
+///   of the displayed error in the automatic `Display` implementation, or all
+///   of them if `*` is given. Defaults to `0`, thus only the direct error of a
+///   variant is shown. Requires `std::error::Error` to be implemented for the
+///   arguments of the variants. Each error of the chain is shown by its
+///   alternate form `{:#}`, which is merely the title and description for
+///   compound errors, as their own sources are shown by the chain anyway.
+/// * `deref`: Additionally implement `Deref` and `AsRef` to the wrapped error
+///   of a `transparent` struct.
+/// * `subset(Name: A, B, C, ...)`: Additionally generate the enum `Name`,
+///   which only contains the variants `A`, `B`, `C`, ... of the target type,
+///   along with `From<Name>` for the target type and `TryFrom<Target>` for
+///   `Name`. Further subsets can be appended, separated by `;`. Not available
+///   for generic target types.
+/// * `trace_from` or `trace_from = "<level>"`: Emit a `tracing` event in each
+///   generated `From` impl, recording the name of the variant and the
+///   `Display` output of the converted error, or its `Debug` output for
+///   `no_source` variants. The level defaults to `"debug"`.
+///   Requires the `tracing` feature of this crate, which provides the
+///   `tracing` crate.
+/// * `result_ext`: Additionally generate the trait `<Target>ResultExt`, which
+///   is implemented for all `Result<T, E>`. It provides `into_<target>()`,
+///   converting the error into the target type, and `or_<variant>()` for each
+///   variant, converting the error into the argument of the respective variant
+///   and wrapping it in that variant. Not available for generic target types.
+/// * `meta = <Type>` or `meta(<Type>, default)`: Additionally generate the
+///   struct `<Target>WithMeta`, holding the target type as `error` and `<Type>`
+///   as `meta`. `Display` and `Error` are forwarded to `error`. The wrapper is
+///   constructed by the generated `with_meta(meta)` method on the target type
+///   or, if `default` is given, via `From` using the `Default` of `<Type>`.
+/// * `variants`: Additionally generate the associated constants `VARIANTS`,
+///   holding the names of all variants, and `VARIANT_COUNT`, holding the number
+///   of variants. Only available for enums.
+/// * `mergeable`: Allow the target type to be given to `merge_errors!` without
+///   listing its variants, which are provided by a hidden macro named like the
+///   target type instead. As a `macro_rules!` macro can not be exported under
+///   the name of a type, merging is limited to the defining crate. Only
+///   available for non-generic enums, whose variants all wrap an error.
+/// * `defmt`: Additionally implement `defmt::Format` for the target type,
+///   mirroring the automatic `Display` implementation on a single line. The
+///   arguments of the variants must implement `defmt::Format` as well.
+///   Variants using `display_with` are shown by their name. Requires the
+///   `defmt` feature of this crate, which provides the `defmt` crate. Note
+///   that this does not make the target type usable in `no_std` crates, as
+///   this crate and the generated code require `std`.
+/// * `arbitrary`: Additionally implement `arbitrary::Arbitrary` for the target
+///   type, choosing any variant (except an `infallible` one) with arbitrary
+///   arguments, which must implement `arbitrary::Arbitrary` as well. Requires
+///   the `test-support` feature of this crate, which provides the `arbitrary`
+///   crate.
+/// * `utoipa`: Additionally implement `utoipa::ToSchema` for the target type,
+///   describing the JSON representation of an error by its `title`,
+///   `message`, `variant` and `code`, as well as `utoipa::IntoResponses`,
+///   listing the variants along with their codes per HTTP status. The method
+///   `http_status()` returning the HTTP status of an error is generated as
+///   well. Requires the `utoipa` feature of this crate, which provides the
+///   `utoipa` crate.
+/// * `status = <status>`: Set the HTTP status of the target type, as used by
+///   `utoipa`, which otherwise defaults to 500. On enums, this is the status of
+///   all variants not specifying their own one.
+/// * `report_fields`: Additionally generate the method `report_fields()`,
+///   returning the title, variant name, code, category, severity and the
+///   rendered source chain of an error as `(name, value)` pairs, as done by
+///   `compound_error::report_fields()`. Requires `std::error::Error` to be
+///   implemented.
+/// * `from_inline` or `from_cold`: Mark the generated `From::from` functions,
+///   converting into the target type, as `#[inline]` or `#[cold]`,
+///   respectively.
+/// * `ffi`: Additionally generate the fieldless `#[repr(C)]` enum
+///   `<Target>Code`, mirroring the variants of the target type with codes
+///   starting at 1, along with the methods `ffi_code()`, returning the code of
+///   an error, and `message_cstr()`, returning its `Display` output as
+///   `CString`. If the target type has explicit discriminants (see below), the
+///   code enum uses its representation and discriminants instead. Variants
+///   removed via `cfg` keep their codes reserved, thus the codes of the other
+///   variants do not depend on the configuration. Only available for enums.
+/// * `code = "<code>"`: Set the code of this error to `"<code>"`, as returned
+///   by `CompoundError::code()`. On enums, this is the code of all variants
+///   not specifying their own one.
+/// * `severity = "<level>"`: Set the severity of this error, as returned by
+///   `CompoundError::severity()`, to one of `"info"`, `"warning"`, `"error"` or
+///   `"critical"`. Defaults to `"error"`. On enums, this is the severity of all
+///   variants not specifying their own one.
+/// * `category = "<category>"`: Set the category of this error to
+///   `"<category>"`, as returned by `CompoundError::category()`. Defaults to
+///   the title. On enums, this is the category of all variants not specifying
+///   their own one.
+/// * `msg_key = "<key>"`: Set the key of the localized message of this error to
+///   `"<key>"`, as returned by `CompoundError::message_key()`. On enums, this
+///   is the key of all variants not specifying their own one. Defaults to the
+///   name of the target type in `snake_case`, followed by the one of the
+///   variant for enums, e.g. `"db_error.timeout"`.
+/// * `url = "<url>"`: Set the URL of the documentation of this error to
+///   `"<url>"`, as returned by `CompoundError::url()`. On enums, this is the
+///   URL of all variants not specifying their own one.
+/// * `url_base = "<base>"`: Set the URL of the documentation of each error
+///   with a code to `"<base><code>"`, unless specified explicitly by `url`.
+/// * `display_url`: Append the URL of the documentation of an error, if any,
+///   to the automatic `Display` output.
+/// * `crate = "<path>"`: Refer to this crate via `<path>` in the generated
+///   code, e.g. `crate = "my_framework::compound_error"`, which allows to use
+///   the derive through a re-export without depending on this crate directly.
+/// * `help = "<help>"`: Set the help text of this error to `"<help>"`, as listed
+///   in the catalog. On enums, this is the help text of all variants not
+///   specifying their own one.
+/// * `catalog`: Additionally generate the associated constant `CATALOG`,
+///   listing a `compound_error::ErrorCatalogEntry` for each variant of the
+///   target type, or a single one for structs. The entries hold the variant
+///   name, title, description, code and help text, where variants are
+///   described by their doc comments, defaulting to the description of the
+///   target type.
+/// * `io_kind = <Kind>`: Additionally implement `From<Target>` for
+///   `std::io::Error`, using the variant `<Kind>` of `std::io::ErrorKind` and
+///   the `Display` output of the target type as message. The kind is also
+///   returned by the generated `io_kind()` method. On enums, this is the kind
+///   of all variants not specifying their own one, which otherwise defaults to
+///   `Other`.
+/// * `from_io(<Kind> => <Variant>, ..., _ => <Variant>)`: Implement
+///   `From<std::io::Error>` for the target type, wrapping the error into the
+///   variant given for its `kind()`. The catch-all arm `_` is required. The
+///   arguments of the given variants must implement `From<std::io::Error>`.
+///   Their own `From` impls are still generated, unless they hold an
+///   `io::Error` themselves.
+///
+/// Enums may be recursive by having variants holding a `Box<Self>` (or a boxed
+/// target type), e.g. `Nested(Box<ParseError>)`. For these, no `From` impl is
+/// generated, and the box is dereferenced as with `source_deref`.
+///
+/// If the target type is an enum with an integer representation, as in
+/// `#[repr(u8)]`, and explicit discriminants, the method `discriminant()`
+/// returning the discriminant of an error is generated as well.
+///
+/// For enums, the method `same_variant(&other)` is generated, which compares
+/// errors by their variant only, ignoring their arguments.
+///
+/// On each enum variant:
+/// * `inline_from(A,B,C,...)`: Inline the Errors `A`, `B`, `C`, ... in the
+///   target type. Generic types can be given directly, e.g.
+///   `inline_from(CompoundFoo<T>)`. For compatibility, types can also be given
+///   as string literals, e.g. `inline_from("CompoundFoo<T>")`. Variants of
+///   the inlined errors, which do not line up with a variant of the target
+///   type, can be handled by a function `fn(A) -> Self` given as in
+///   `inline_from(A, with = map_a)`, which is called for all variants of `A`
+///   not inlined explicitly. Alternatively, these can be routed into a
+///   catch-all variant of the target type given as in
+///   `inline_from(A, fallback = Other)`, whose argument must implement
+///   `From<A>`, e.g. a `Box<dyn Error>`. If the catch-all variant is marked as
+///   `message`, the `Display` output of `A` is used instead. This also covers
+///   variants added in the future to an `A` marked as `#[non_exhaustive]`.
+/// * `prefer_from`: If several variants wrap the same type, as in
+///   `Read(io::Error)` and `Write(io::Error)`, only the one marked as
+///   `prefer_from` gets the `From` impl for it. Without it, such variants are
+///   rejected, as their `From` impls would conflict. As a macro can not
+///   resolve types, such variants are only detected if their types are written
+///   the same way, e.g. `io::Error` and `std::io::Error` are not detected and
+///   result in the conflicting `From` impls instead.
+/// * `no_source`: Return `None` from `<Self as std::error::Error>::source()`
+///   for this enum variant. This lifts the requirement that `std::error::Error`
+///   is implemented for the argument of this variant.
+/// * `convert_source(fn)`: Applies `fn` to the error of this variant before
+///   returing it from `<Self as std::error::Error>::source()`. Instead of a
+///   path to a function, any callable expression can be given, e.g. a closure
+///   like `convert_source(|x| x.inner())`.
+/// * `source_deref`: Dereference the argument of this variant, e.g. an
+///   `Arc<E>`, `Rc<E>` or `Box<E>`, before returning it from
+///   `<Self as std::error::Error>::source()` and before formatting it in the
+///   automatic `Display` implementation.
+/// * `display_converted`: Use the result of `convert_source` instead of the
+///   plain argument of this variant in the automatic `Display` implementation.
+/// * `display_with = fn`: Format this variant by calling `fn` instead of using
+///   the generated message. `fn` must have the signature
+///   `fn(&Arg, &mut std::fmt::Formatter) -> std::fmt::Result`, where `Arg` is
+///   the argument of this variant.
+/// * `transparent`: forward the source and Display methods through to the
+///   argument of this variant without adding an additional message.
+/// * `infallible`: Mark the argument of this variant as uninhabited (e.g.
+///   `core::convert::Infallible` or `!`). Instead of the usual `From` impl for
+///   the argument, `From<core::convert::Infallible>` is implemented. At most one
+///   variant can be marked as `infallible`.
+/// * `map`: Additionally generate the method `map_<variant>(f)` for this
+///   variant, whose argument must be a generic type parameter `T` not used by
+///   any other variant. It maps the argument via `f: FnOnce(T) -> U`, turning
+///   the target type with `T` into the one with `U`, while preserving all other
+///   variants.
+/// * `optional`: Mark the argument of this variant as optional error, as in
+///   `Cause(Option<E>)`. `source()` returns the contained error, if any, while
+///   `Display` shows the name of the variant in case of `None`. The generated
+///   `From` impls convert from `E`, wrapping it in `Some`.
+/// * `message`: Mark this variant as holding a plain message, e.g. a `String`
+///   or a `Cow<'static, str>`. Instead of the usual `From` impl for the
+///   argument, `From<String>` and `From<&str>` are implemented. Like with
+///   `no_source`, `source()` returns `None`, while `Display` shows the message.
+///   At most one variant can be marked as `message`.
+/// * `location`: Capture the location at which an error is converted into
+///   this variant via its `From` impls. The variant must have a second unnamed
+///   field of type `&'static std::panic::Location<'static>` holding the
+///   location, which is added automatically when using the
+///   [`compound_error`](attr.compound_error.html) attribute. The location is
+///   returned by the generated `location()` method.
+/// * `context`: Mark this variant as holding a message and another instance of
+///   the target type, as in `WithContext(String, Box<Self>)`. The generated
+///   `context(msg)` method wraps an error into this variant, while `Display`
+///   prepends the message to the wrapped error. No `From` impl is generated
+///   for this variant. Using the [`compound_error`](attr.compound_error.html)
+///   attribute, the variant `Context(String, Box<Self>)` can be added
+///   automatically by giving `context` on the target type.
+/// * `cfg(<predicate>)`: Only include this variant along with all code
+///   concerning it (`From` impls, `Display` and `source()` arms, ...) if
+///   `<predicate>` holds, as with `#[cfg(<predicate>)]`. Requires the
+///   [`compound_error`](attr.compound_error.html) attribute, as the derive
+///   alone can not remove the variant itself. With the derive, plain
+///   `#[cfg(...)]` attributes of variants are to be used instead, which are
+///   honored the same way by the attribute.
+/// * `ignore`: Skip this variant entirely. No `From` impls are generated for
+///   it, and it neither needs to take exactly one argument nor to be an error.
+///   The automatic `Display` implementation prints nothing for it and
+///   `source()` returns `None`, thus these are usually provided manually in
+///   conjunction with `skip_display` or `skip_error`. Only the attributes
+///   describing the variant, i.e. `code`, `url`, `status`, `severity`,
+///   `category`, `help`, `msg_key` and `io_kind`, can be combined with it.
+/// * `code = "<code>"`: Set the code of this variant to `"<code>"`, as returned
+///   by `CompoundError::code()`.
+/// * `severity = "<level>"`: Set the severity of this variant, as returned by
+///   `CompoundError::severity()`. See `severity` on the target type.
+/// * `category = "<category>"`: Set the category of this variant to
+///   `"<category>"`, as returned by `CompoundError::category()`.
+/// * `msg_key = "<key>"`: Set the key of the localized message of this variant
+///   to `"<key>"`, as returned by `CompoundError::message_key()`.
+/// * `group = "<name>"`: Assign this variant to the group `<name>`, given in
+///   `snake_case`. For each group, the enum `<Target><Name>` holding the
+///   variants of the group is generated as with `subset`, e.g. the group
+///   `"network"` of `ApiError` results in `ApiErrorNetwork`. Additionally, the
+///   enum `<Target>Group` listing all groups and the method `group()`
+///   returning the group of an error, if any, are generated. Not available for
+///   generic target types.
+/// * `status = <status>`: Set the HTTP status of this variant, as used by
+///   `utoipa`. See `status` on the target type.
+/// * `url = "<url>"`: Set the URL of the documentation of this variant to
+///   `"<url>"`, as returned by `CompoundError::url()`.
+/// * `help = "<help>"`: Set the help text of this variant to `"<help>"`, as
+///   listed in the catalog.
+/// * `io_kind = <Kind>`: Convert this variant into a `std::io::Error` of kind
+///   `<Kind>`. Implies the `From<Target>` impl for `std::io::Error`, see
+///   `io_kind` on the target type.
+///
+#[proc_macro_derive(CompoundError, attributes(compound_error))]
+pub fn derive_compound_error(input: TokenStream) -> TokenStream {
+	let input = parse_macro_input!(input as DeriveInput);
+	expand(input)
+}
+
+/// Item-level alternative to `#[derive(CompoundError)]`.
+///
+/// Generates the same impls as the derive, but additionally rewrites the
+/// target type itself:
+/// * `#[derive(Debug)]` is added to the target type unless it is already
+///   derived or `skip_debug` is given.
+/// * All `#[compound_error(...)]` helper attributes are removed from the
+///   target type and its variants.
+///
+/// The arguments of the attribute are the ones accepted on the target type by
+/// the derive (see [`CompoundError`](derive.CompoundError.html)), plus
+/// `skip_debug` and `context`. Variants are annotated just as with the derive:
+///
+/// ```text
+/// #[compound_error::compound_error( title = "Read Error" )]
+/// pub enum ReadError {
+///     #[compound_error( inline_from(OpenError) )]
+///     FileNotFound(FileNotFound),
+///     Io(std::io::Error),
+/// }
+/// ```
+///
+/// Referencing the attribute by its full path as above avoids ambiguities
+/// with the helper attribute of the derive.
+#[proc_macro_attribute]
+pub fn compound_error(args: TokenStream, input: TokenStream) -> TokenStream {
+	let input = parse_macro_input!(input as DeriveInput);
+	attribute::expand(args.into(), input)
+}
+
+/// Defines compound error enums with a terse syntax.
+///
+/// Each enum is declared as usual, except that the arguments of the
+/// `#[compound_error(...)]` helper attribute of a variant are written directly
+/// after the variant, separated by spaces. Arguments taking a value are given
+/// in their list form, e.g. `display_with(display_fn)`. Every enum gets
+/// `#[derive(Debug)]` and the `CompoundError` impls, just as with the
+/// [`compound_error`](attr.compound_error.html) attribute.
+///
+/// ```text
+/// compound_errors! {
+///     #[compound_error( title = "API Error" )]
+///     pub enum ApiError {
+///         Io(std::io::Error),
+///         Parse(ParseError) inline_from(LowLevel) no_source,
+///     }
+///
+///     pub enum LowLevel {
+///         Parse(ParseError),
+///     }
+/// }
+/// ```
+///
+/// Several enums can be defined within a single invocation.
+///
+/// The macro is named `compound_errors!` rather than `compound_error!`,
+/// because attribute and function-like macros share a namespace, in which
+/// `compound_error` already names the attribute.
+#[proc_macro]
+pub fn compound_errors(input: TokenStream) -> TokenStream {
+	definition::expand(input.into())
+}
+
+/// Merges existing compound error enums into a new one.
+///
+/// The merged enum contains the union of the variants of all given enums,
+/// where variants of the same name are only contained once. Additionally,
+/// `From` is implemented for each of the merged enums (see `inline_from`),
+/// and the merged enum gets `#[derive(Debug)]` and the `CompoundError` impls.
+///
+/// Because a macro can not look into the definitions of other types, the
+/// variants of the merged enums are provided by them: Enums marked as
+/// `mergeable` (see `CompoundError`) come with a hidden macro listing their
+/// variants, thus they are merged by their name only.
+///
+/// ```text
+/// merge_errors! {
+///     #[compound_error( title = "Combined Error" )]
+///     pub enum Combined = NetworkError + StorageError;
+/// }
+/// ```
+///
+/// That macro is only visible within the defining crate. Enums of other crates
+/// and generic enums are merged by repeating their variants instead. Since the
+/// generated `From` impls match exhaustively on the merged enums, forgetting a
+/// variant results in a compile error.
+///
+/// ```text
+/// merge_errors! {
+///     pub enum Combined = NetworkError + other_crate::StorageError {
+///         Io(std::io::Error),
+///         Full(DiskFull),
+///     };
+/// }
+/// ```
+///
+/// In both cases, the argument types of the variants have to be in scope where
+/// the merged enum is defined.
+#[proc_macro]
+pub fn merge_errors(input: TokenStream) -> TokenStream {
+	merge::expand(input.into())
+}
+
+/// Generates the `CompoundError` impls for the given target type.
+fn expand(input: DeriveInput) -> TokenStream {
+	let original_input = input.clone();
+	let ident = input.ident.clone();
+	let generics = input.generics;
+	let (generics_impl, generics_type, generics_where) = generics.split_for_impl();
+
+	let mut toplevel_args = try_compile!(
+		attr_args(
+			&input.attrs,
+			"compound_error",
+			&[
+				"title",
+				"description",
+				"skip_display",
+				"skip_error",
+				"transparent",
+				"subset",
+				"trace_from",
+				"result_ext",
+				"meta",
+				"variants",
+				"mergeable",
+				"code",
+				"severity",
+				"category",
+				"io_kind",
+				"from_io",
+				"ffi",
+				"defmt",
+				"report_fields",
+				"from_inline",
+				"from_cold",
+				"deref",
+				"chain_depth",
+				"msg_key",
+				"catalog",
+				"help",
+				"url",
+				"url_base",
+				"display_url",
+				"arbitrary",
+				"utoipa",
+				"status",
+				"crate",
+			]
+		),
+		|err| err.explain()
+	);
+
+	let title_attr = toplevel_args.remove(&"title");
+	let title = {
+		if let Some(attr) = title_attr {
+			match attr.parse_value::<syn::LitStr>() {
+				Ok(lit) => lit.value(),
+				Err(_) => return error(&attr.path, "'title' takes exactly one string argument!"),
+			}
+		} else {
+			ident.to_string()
+		}
+	};
+
+	let description_attr = toplevel_args.remove(&"description");
+	let description_value = {
+		if let Some(attr) = &description_attr {
+			match attr.parse_value::<syn::LitStr>() {
+				Ok(lit) => Some(lit.value()),
+				Err(_) => {
+					return error(
+						&attr.path,
+						"'description' takes exactly one string argument!",
+					)
+				},
+			}
+		} else {
+			None
+		}
+	};
+	let description = {
+		match &description_value {
+			Some(value) => format!(" ({})", value),
+			None => "".into(),
+		}
+	};
+
+	let krate = {
+		if let Some(attr) = toplevel_args.get(&"crate") {
+			match attr
+				.parse_value::<syn::LitStr>()
+				.and_then(|lit| lit.parse::<syn::Path>())
+			{
+				Ok(path) => quote!(#path),
+				Err(_) => {
+					return error(
+						&attr.path,
+						"'crate' takes exactly one string argument, the path to this crate!",
+					)
+				},
+			}
+		} else {
+			quote!(::compound_error)
+		}
+	};
+
+	let code_attr = toplevel_args.remove(&"code");
+	let code = {
+		if let Some(attr) = &code_attr {
+			match attr.parse_value::<syn::LitStr>() {
+				Ok(lit) => Some(lit.value()),
+				Err(_) => return error(&attr.path, "'code' takes exactly one string argument!"),
+			}
+		} else {
+			None
+		}
+	};
+
+	let category_attr = toplevel_args.remove(&"category");
+	let category = {
+		if let Some(attr) = &category_attr {
+			match attr.parse_value::<syn::LitStr>() {
+				Ok(lit) => lit.value(),
+				Err(_) => {
+					return error(&attr.path, "'category' takes exactly one string argument!")
+				},
+			}
+		} else {
+			title.clone()
+		}
+	};
+
+	let io_kind_attr = toplevel_args.remove(&"io_kind");
+	let io_kind = {
+		if let Some(attr) = &io_kind_attr {
+			Some(try_compile!(attr.parse_value::<Ident>(), |_err| {
+				error(
+					&attr.path,
+					"'io_kind' takes exactly one variant of 'std::io::ErrorKind'!",
+				)
+			}))
+		} else {
+			None
+		}
+	};
+
+	let from_io_attr = toplevel_args.remove(&"from_io");
+	let from_io = {
+		if let Some(attr) = &from_io_attr {
+			let arms = try_compile!(attr.parse_list::<FromIoArm>(), |_err| {
+				error(
+					&attr.path,
+					"'from_io' takes a list of arms as in 'from_io(NotFound => Missing, _ => Io)'!",
+				)
+			});
+			if !arms.iter().any(|arm| arm.kind.is_none()) {
+				return error(
+					&attr.path,
+					"'from_io' requires a catch-all arm as in '_ => Io'!",
+				);
+			}
+			arms
+		} else {
+			Vec::new()
+		}
+	};
+
+	let msg_key_attr = toplevel_args.remove(&"msg_key");
+	let msg_key = {
+		if let Some(attr) = &msg_key_attr {
+			match attr.parse_value::<syn::LitStr>() {
+				Ok(lit) => Some(lit.value()),
+				Err(_) => return error(&attr.path, "'msg_key' takes exactly one string argument!"),
+			}
+		} else {
+			None
+		}
+	};
+
+	let url_base = {
+		if let Some(attr) = toplevel_args.get(&"url_base") {
+			match attr.parse_value::<syn::LitStr>() {
+				Ok(lit) => Some(lit.value()),
+				Err(_) => {
+					return error(&attr.path, "'url_base' takes exactly one string argument!")
+				},
+			}
+		} else {
+			None
+		}
+	};
+
+	// The URL of the given code, if a base URL is given
+	let code_url = |code: &str| url_base.as_ref().map(|base| format!("{}{}", base, code));
+
+	let url = {
+		if let Some(attr) = toplevel_args.get(&"url") {
+			match attr.parse_value::<syn::LitStr>() {
+				Ok(lit) => Some(lit.value()),
+				Err(_) => return error(&attr.path, "'url' takes exactly one string argument!"),
+			}
+		} else {
+			code.as_deref().and_then(code_url)
+		}
+	};
+
+	let status = {
+		if let Some(attr) = toplevel_args.remove(&"status") {
+			match attr
+				.parse_value::<syn::LitInt>()
+				.and_then(|lit| lit.base10_parse::<u16>())
+			{
+				Ok(status) => status,
+				Err(_) => return error(&attr.path, "'status' takes exactly one HTTP status code!"),
+			}
+		} else {
+			500
+		}
+	};
+
+	let help_attr = toplevel_args.remove(&"help");
+	let help = {
+		if let Some(attr) = &help_attr {
+			match attr.parse_value::<syn::LitStr>() {
+				Ok(lit) => Some(lit.value()),
+				Err(_) => return error(&attr.path, "'help' takes exactly one string argument!"),
+			}
+		} else {
+			None
+		}
+	};
+
+	let severity_attr = toplevel_args.remove(&"severity");
+	let severity = {
+		match &severity_attr {
+			Some(attr) => try_compile!(parse_severity(attr, &krate), |err| err),
+			None => quote!(#krate::Severity::Error),
+		}
+	};
+
+	let from_attrs = {
+		match (
+			flag!(&toplevel_args, &"from_inline"),
+			flag!(&toplevel_args, &"from_cold"),
+		) {
+			(true, true) => {
+				return error(
+					&toplevel_args[&"from_cold"].path,
+					"'from_cold' can not be combined with 'from_inline'!",
+				)
+			},
+			(true, false) => quote!(#[inline]),
+			(false, true) => quote!(#[cold]),
+			(false, false) => proc_macro2::TokenStream::new(),
+		}
+	};
+
+	// The number of further levels of the source chain shown by `Display`
+	let chain_depth = {
+		if let Some(attr) = toplevel_args.get(&"chain_depth") {
+			let depth = {
+				match &attr.value {
+					AttrValue::Value(value) if value.to_string() == "*" => Ok(usize::MAX),
+					_ => {
+						attr.parse_value::<syn::LitInt>()
+							.and_then(|lit| lit.base10_parse::<usize>())
+					},
+				}
+			};
+			match depth {
+				Ok(0) => None,
+				Ok(usize::MAX) => Some(quote!(::core::primitive::usize::MAX)),
+				Ok(depth) => Some(quote!(#depth)),
+				Err(_) => {
+					return error(
+						&attr.path,
+						"'chain_depth' takes either a number or '*' for the full chain!",
+					)
+				},
+			}
+		} else {
+			None
+		}
+	};
+
+	// Writes the sources of `source` up to `chain_depth`, nested below the
+	// displayed error. Each one is shown by its alternate form, which is merely
+	// its title and description for compound errors, as its own sources follow
+	// anyway.
+	let display_chain = quote! {
+		fn display_chain(
+			f: &mut ::core::fmt::Formatter<'_>,
+			mut source: ::core::option::Option<&(dyn ::std::error::Error + 'static)>,
+			depth: usize,
+		) -> ::core::fmt::Result {
+			let mut level = 0;
+			while let ::core::option::Option::Some(cause) = source {
+				if level == depth {
+					break;
+				}
+				level += 1;
+				::core::write!(f, "\n{:indent$}└ {:#}", "", cause, indent = 2 + 2 * level)?;
+				source = ::std::error::Error::source(cause);
+			}
+			::core::result::Result::Ok(())
+		}
+	};
+
+	let skip_display = flag!(&toplevel_args, &"skip_display");
+	let defmt = flag!(&toplevel_args, &"defmt");
+	if defmt && !cfg!(feature = "defmt") {
+		return error(
+			&toplevel_args[&"defmt"].path,
+			"'defmt' requires the 'defmt' feature!",
+		);
+	}
+	let arbitrary = flag!(&toplevel_args, &"arbitrary");
+	if arbitrary && !cfg!(feature = "test-support") {
+		return error(
+			&toplevel_args[&"arbitrary"].path,
+			"'arbitrary' requires the 'test-support' feature!",
+		);
+	}
+	let utoipa = flag!(&toplevel_args, &"utoipa");
+	if utoipa && !cfg!(feature = "utoipa") {
+		return error(
+			&toplevel_args[&"utoipa"].path,
+			"'utoipa' requires the 'utoipa' feature!",
+		);
+	}
+	let skip_error = flag!(&toplevel_args, &"skip_error");
+
+	let trace_level = {
+		if let Some(attr) = toplevel_args.remove(&"trace_from") {
+			if !cfg!(feature = "tracing") {
+				return error(&attr.path, "'trace_from' requires the 'tracing' feature!");
+			}
+
+			let level = {
+				match attr.value {
+					AttrValue::Flag => "debug".to_string(),
+					_ => {
+						match attr.parse_value::<syn::LitStr>() {
+							Ok(lit) => lit.value(),
+							Err(_) => {
+								return error(
+									&attr.path,
+									"'trace_from' takes at most one string argument!",
+								)
+							},
+						}
+					},
+				}
+			};
+
+			if !["error", "warn", "info", "debug", "trace"].contains(&level.as_str()) {
+				return error(
+					&attr.path,
+					"'trace_from' level must be one of \"error\", \"warn\", \"info\", \"debug\" \
+					 or \"trace\"!",
+				);
+			}
+
+			Some(Ident::new(&level, proc_macro2::Span::call_site()))
+		} else {
+			None
+		}
+	};
+
+	let mut generated = proc_macro2::TokenStream::new();
+
+	if let Some(deref_attr) = toplevel_args.get(&"deref") {
+		let transparent_struct =
+			matches!(input.data, Data::Struct(_)) && toplevel_args.contains_key(&"transparent");
+		if !transparent_struct {
+			return error(
+				&deref_attr.path,
+				"'deref' can only be used on 'transparent' structs!",
+			);
+		}
+	}
+
+	// Subsets and groups share the look of the target type
+	let mut subset_args = vec![quote!(title = #title)];
+	for attr in description_attr
+		.iter()
+		.chain(&code_attr)
+		.chain(&severity_attr)
+		.chain(&category_attr)
+		.chain(&io_kind_attr)
+	{
+		subset_args.push(quote!(#attr));
+	}
+	for key in &[
+		"skip_display",
+		"skip_error",
+		"transparent",
+		"from_inline",
+		"from_cold",
+		"chain_depth",
+		"url",
+		"url_base",
+		"display_url",
+		"crate",
+	] {
+		if let Some(attr) = toplevel_args.get(key) {
+			subset_args.push(quote!(#attr));
+		}
+	}
+
+	if let Some(subset_attr) = toplevel_args.remove(&"subset") {
+		let subsets = try_compile!(subset::parse_subsets(&subset_attr), |err| {
+			err.to_compile_error().into()
+		});
+
+		for subset in &subsets {
+			generated.extend(try_compile!(
+				subset::generate(subset, &original_input, &subset_args, &krate),
+				|err| err.to_compile_error().into()
+			));
+		}
+	}
+
+	#[allow(unused_assignments)]
+	let mut err_source = proc_macro2::TokenStream::new();
+	let mut from_enums: HashMap<syn::TypePath, Vec<(Ident, Type, Span)>> = HashMap::new();
+	let mut from_enum_rest: HashMap<syn::TypePath, InlineRest> = HashMap::new();
+	let mut from_structs: Vec<(Type, Ident, bool)> = Vec::new();
+	let mut from_infallible: Option<Ident> = None;
+	let mut message_variant: Option<Ident> = None;
+	let mut map_variants: Vec<(Ident, Ident)> = Vec::new();
+	let mut optional_variants: Vec<Ident> = Vec::new();
+	let mut location_variants: Vec<Ident> = Vec::new();
+	// Variants whose arguments are not shown as source
+	let mut no_source_variants: Vec<Ident> = Vec::new();
+	let mut context_variant: Option<(Ident, Type)> = None;
+	let mut variant_cfgs: HashMap<Ident, proc_macro2::TokenStream> = HashMap::new();
+	let mut all_variants: Vec<Ident> = Vec::new();
+	let mut payload_variants: Vec<(Ident, Type)> = Vec::new();
+	let mut variant_codes: Vec<(Ident, proc_macro2::TokenStream)> = Vec::new();
+	let mut variant_severities: Vec<(Ident, proc_macro2::TokenStream)> = Vec::new();
+	let mut variant_categories: Vec<(Ident, proc_macro2::TokenStream)> = Vec::new();
+	let mut variant_msg_keys: HashMap<Ident, String> = HashMap::new();
+	let mut variant_helps: HashMap<Ident, String> = HashMap::new();
+	let mut variant_urls: Vec<(Ident, proc_macro2::TokenStream)> = Vec::new();
+	let mut variant_statuses: Vec<(Ident, u16)> = Vec::new();
+	let mut variant_groups: Vec<(Ident, Ident)> = Vec::new();
+	let mut variant_docs: HashMap<Ident, String> = HashMap::new();
+	let mut variant_io_kinds: Vec<(Ident, proc_macro2::TokenStream)> = Vec::new();
+
+	#[allow(unused_assignments)]
+	let mut display = proc_macro2::TokenStream::new();
+	#[allow(unused_assignments)]
+	let mut defmt_format = proc_macro2::TokenStream::new();
+
+	match input.data {
+		Data::Enum(data) => {
+			let transparent_enum = flag!(&toplevel_args, &"transparent");
+
+			let mut err_sources = proc_macro2::TokenStream::new();
+
+			let mut display_cases = Vec::new();
+			let mut defmt_cases = Vec::new();
+			// Whether any variant is formatted by the outlined `display_variant`
+			let mut outline_display = false;
+			// Whether any variant shows its sources by `display_chain`
+			let mut outline_chain = false;
+
+			for variant in data.variants {
+				let variant_ident = variant.ident;
+				let variant_ident_str = variant_ident.to_string();
+				let mut args = {
+					match attr_args(
+						&variant.attrs,
+						"compound_error",
+						&[
+							"inline_from",
+							"skip_single_from",
+							"no_source",
+							"convert_source",
+							"transparent",
+							"infallible",
+							"display_converted",
+							"display_with",
+							"location",
+							"context",
+							"cfg",
+							"ignore",
+							"code",
+							"severity",
+							"category",
+							"io_kind",
+							"message",
+							"source_deref",
+							"map",
+							"optional",
+							"msg_key",
+							"help",
+							"url",
+							"prefer_from",
+							"status",
+							"group",
+						],
+					) {
+						Err(err) => return err.explain(),
+						Ok(ok) => ok,
+					}
+				};
+
+				// Conditions of the variant, applying to all generated code concerning it
+				let mut cfg = proc_macro2::TokenStream::new();
+				for attr in &variant.attrs {
+					if attr.path.is_ident("cfg") {
+						cfg.extend(quote!(#attr));
+					}
+				}
+				if let Some(cfg_attr) = args.remove(&"cfg") {
+					let predicate = {
+						match &cfg_attr.value {
+							AttrValue::List(predicate) => predicate,
+							_ => {
+								return error(
+									&cfg_attr.path,
+									"'cfg' takes a configuration predicate as in 'cfg(feature = \
+									 \"x\")'!",
+								)
+							},
+						}
+					};
+					// The derive alone can not remove the variant itself, which is
+					// done by the attribute via a plain `#[cfg(...)]`
+					let predicate = quote!(( #predicate )).to_string();
+					if !variant.attrs.iter().any(|attr| {
+						attr.path.is_ident("cfg") && attr.tokens.to_string() == predicate
+					}) {
+						return error(
+							&cfg_attr.path,
+							"'cfg' requires the #[compound_error] attribute, which removes the \
+							 variant as well! With the derive alone, use a plain #[cfg(...)] on \
+							 the variant instead.",
+						);
+					}
+				}
+				if !cfg.is_empty() {
+					variant_cfgs.insert(variant_ident.clone(), cfg.clone());
+				}
+
+				// Ignored variants are merely described, thus anything concerning
+				// their conversion or formatting is rejected
+				let ignore = flag!(&args, &"ignore");
+				if ignore {
+					let describing = [
+						"ignore", "code", "url", "status", "severity", "category", "help",
+						"msg_key", "io_kind",
+					];
+					if let Some((key, attr)) = args
+						.iter()
+						.filter(|(key, _)| !describing.contains(*key))
+						.min_by_key(|(key, _)| **key)
+					{
+						return error(
+							&attr.path,
+							&format!("'{}' can not be used on an 'ignore' variant!", key),
+						);
+					}
+				}
+
+				all_variants.push(variant_ident.clone());
+
+				let mut variant_url = None;
+				if let Some(code_attr) = args.remove(&"code") {
+					match code_attr.parse_value::<syn::LitStr>() {
+						Ok(lit) => {
+							variant_url = code_url(&lit.value());
+							variant_codes.push((
+								variant_ident.clone(),
+								quote!(::core::option::Option::Some(#lit)),
+							))
+						},
+						Err(_) => {
+							return error(
+								&code_attr.path,
+								"'code' takes exactly one string argument!",
+							)
+						},
+					}
+				}
+				if let Some(url_attr) = args.remove(&"url") {
+					match url_attr.parse_value::<syn::LitStr>() {
+						Ok(lit) => variant_url = Some(lit.value()),
+						Err(_) => {
+							return error(
+								&url_attr.path,
+								"'url' takes exactly one string argument!",
+							)
+						},
+					}
+				}
+				if let Some(variant_url) = variant_url {
+					variant_urls.push((
+						variant_ident.clone(),
+						quote!(::core::option::Option::Some(#variant_url)),
+					));
+				}
+
+				if let Some(status_attr) = args.remove(&"status") {
+					match status_attr
+						.parse_value::<syn::LitInt>()
+						.and_then(|lit| lit.base10_parse::<u16>())
+					{
+						Ok(status) => {
+							variant_statuses.push((variant_ident.clone(), status));
+						},
+						Err(_) => {
+							return error(
+								&status_attr.path,
+								"'status' takes exactly one HTTP status code!",
+							)
+						},
+					}
+				}
+
+				if let Some(group_attr) = args.remove(&"group") {
+					let group = {
+						match group_attr.parse_value::<syn::LitStr>() {
+							Ok(lit) => {
+								syn::parse_str::<Ident>(&to_camel_case(&lit.value()))
+									.map(|group| Ident::new(&group.to_string(), lit.span()))
+							},
+							Err(err) => Err(err),
+						}
+					};
+					match group {
+						Ok(group) => variant_groups.push((variant_ident.clone(), group)),
+						Err(_) => {
+							return error(
+								&group_attr.path,
+								"'group' takes exactly one string argument, which is a valid \
+								 identifier in snake_case!",
+							)
+						},
+					}
+				}
+
+				if let Some(severity_attr) = args.remove(&"severity") {
+					let severity = try_compile!(parse_severity(&severity_attr, &krate), |err| err);
+					variant_severities.push((variant_ident.clone(), severity));
+				}
+
+				if let Some(category_attr) = args.remove(&"category") {
+					match category_attr.parse_value::<syn::LitStr>() {
+						Ok(lit) => variant_categories.push((variant_ident.clone(), quote!(#lit))),
+						Err(_) => {
+							return error(
+								&category_attr.path,
+								"'category' takes exactly one string argument!",
+							)
+						},
+					}
+				}
+
+				if let Some(help_attr) = args.remove(&"help") {
+					match help_attr.parse_value::<syn::LitStr>() {
+						Ok(lit) => {
+							variant_helps.insert(variant_ident.clone(), lit.value());
+						},
+						Err(_) => {
+							return error(
+								&help_attr.path,
+								"'help' takes exactly one string argument!",
+							)
+						},
+					}
+				}
+
+				if let Some(doc) = doc_comment(&variant.attrs) {
+					variant_docs.insert(variant_ident.clone(), doc);
+				}
+
+				if let Some(msg_key_attr) = args.remove(&"msg_key") {
+					match msg_key_attr.parse_value::<syn::LitStr>() {
+						Ok(lit) => {
+							variant_msg_keys.insert(variant_ident.clone(), lit.value());
+						},
+						Err(_) => {
+							return error(
+								&msg_key_attr.path,
+								"'msg_key' takes exactly one string argument!",
+							)
+						},
+					}
+				}
+
+				if let Some(io_kind_attr) = args.remove(&"io_kind") {
+					let kind = try_compile!(io_kind_attr.parse_value::<Ident>(), |_err| {
+						error(
+							&io_kind_attr.path,
+							"'io_kind' takes exactly one variant of 'std::io::ErrorKind'!",
+						)
+					});
+					variant_io_kinds
+						.push((variant_ident.clone(), quote!(::std::io::ErrorKind::#kind)));
+				}
+
+				if ignore {
+					continue;
+				}
+
+				if flag!(&args, &"context") {
+					let message_type = {
+						match &variant.fields {
+							Fields::Unnamed(fields) if fields.unnamed.len() == 2 => {
+								&fields.unnamed[0].ty
+							},
+							_ => {
+								return error(
+									&original_input,
+									&format!(
+										"Context variant '{}' must specify exactly two unnamed \
+										 fields, the message and the boxed inner error!",
+										variant_ident
+									),
+								)
+							},
+						}
+					};
+
+					if let Some((other, _)) = &context_variant {
+						return error(
+							&variant_ident,
+							&format!("Variant '{}' is already marked as 'context'!", other),
+						);
+					}
+					context_variant = Some((variant_ident.clone(), message_type.clone()));
+
+					err_sources.extend(quote! {
+						#cfg Self::#variant_ident(_, inner) => ::core::option::Option::Some( &**inner ),
+					});
+					display_cases.push(quote! {
+						#cfg Self::#variant_ident (message, inner) => {
+							if f.alternate() {
+								::core::write!(f, "{}", message)?;
+							} else {
+								::core::write!(f, "{}: {}", message, inner)?;
+							}
+						}
+					});
+					defmt_cases.push(quote! {
+						#cfg Self::#variant_ident (message, inner) => {
+							defmt::write!(
+								f,
+								"{=str}: {}",
+								::core::convert::AsRef::<str>::as_ref(message),
+								&**inner,
+							);
+						}
+					});
+
+					continue;
+				}
+
+				let location = flag!(&args, &"location");
+
+				let field = {
+					match variant.fields {
+						Fields::Unnamed(fields) if fields.unnamed.len() == 1 && !location => {
+							fields.unnamed[0].clone()
+						},
+						Fields::Unnamed(fields) if fields.unnamed.len() == 2 && location => {
+							fields.unnamed[0].clone()
+						},
+						_ if location => {
+							return error(
+								&original_input,
+								&format!(
+									"Variant '{}' must specify exactly two unnamed fields, the \
+									 second one being the location!",
+									variant_ident
+								),
+							)
+						},
+						_ => {
+							return error(
+								&original_input,
+								&format!(
+									"Variant '{}' must specify exactly one unnamed field!",
+									variant_ident
+								),
+							)
+						},
+					}
+				};
+
+				let optional = flag!(&args, &"optional");
+				let primitive_type = {
+					if optional {
+						for key in &[
+							"convert_source",
+							"display_converted",
+							"source_deref",
+							"message",
+							"no_source",
+							"infallible",
+							"map",
+							"display_with",
+						] {
+							if let Some(attr) = args.get(key) {
+								return error(
+									&attr.path,
+									&format!("'{}' can not be combined with 'optional'!", key),
+								);
+							}
+						}
+
+						match option_inner(&field.ty) {
+							Some(inner) => inner.clone(),
+							None => {
+								return error(
+									&field.ty,
+									&format!(
+										"Optional variant '{}' must take an 'Option<E>'!",
+										variant_ident
+									),
+								)
+							},
+						}
+					} else {
+						field.ty
+					}
+				};
+
+				if location {
+					location_variants.push(variant_ident.clone());
+				}
+				if optional {
+					optional_variants.push(variant_ident.clone());
+				}
+
+				if let Some(from_attr) = args.remove(&"inline_from") {
+					let values = try_compile!(from_attr.parse_list::<InlineFromArg>(), |err| {
+						let mut err = err.clone();
+						err.combine(syn::Error::new_spanned(
+							&from_attr.path,
+							"'inline_from' attribute must be a list of types!",
+						));
+						err.to_compile_error().into()
+					});
+
+					let mut types = Vec::new();
+					let mut rest = None;
+					for value in values {
+						match value {
+							InlineFromArg::Type(inline_from) => types.push(inline_from),
+							InlineFromArg::Rest(_) if rest.is_some() => {
+								return error(
+									&from_attr.path,
+									"Only one of 'with' and 'fallback' can be given!",
+								);
+							},
+							InlineFromArg::Rest(value) => rest = Some(value),
+						}
+					}
+
+					for inline_from in types {
+						if let Some(rest) = &rest {
+							if from_enum_rest.contains_key(&inline_from.ty) {
+								return error(
+									&inline_from.ty,
+									"The remaining variants of this type are already handled via \
+									 'with' or 'fallback'!",
+								);
+							}
+							from_enum_rest.insert(inline_from.ty.clone(), rest.clone());
+						}
+
+						from_enums.entry(inline_from.ty).or_default().push((
+							variant_ident.clone(),
+							primitive_type.clone(),
+							inline_from.span,
+						));
+					}
+				}
+
+				let skip_single_from = flag!(&args, &"skip_single_from");
+				let transparent = flag!(&args, &"transparent") || transparent_enum;
+
+				if flag!(&args, &"infallible") {
+					if let Some(other) = &from_infallible {
+						return error(
+							&variant_ident,
+							&format!(
+								"Variant '{}' is already marked as 'infallible'!",
+								other
+							),
+						);
+					}
+					from_infallible = Some(variant_ident.clone());
+
+					// The payload is uninhabited, thus there is nothing to display or
+					// to return as source.
+					err_sources.extend(quote! {
+						#cfg Self::#variant_ident(x, ..) => match *x {},
+					});
+					display_cases.push(quote! {
+						#cfg Self::#variant_ident (x, ..) => {
+							match *x {}
+						}
+					});
+					defmt_cases.push(quote! {
+						#cfg Self::#variant_ident (x, ..) => {
+							match *x {}
+						}
+					});
+
+					continue;
+				}
+
+				payload_variants.push((variant_ident.clone(), primitive_type.clone()));
+
+				if let Some(map_attr) = args.get(&"map") {
+					if !matches!(map_attr.value, AttrValue::Flag) {
+						return error(&map_attr.path, "'map' attribute takes no arguments!");
+					}
+
+					let param = {
+						match &primitive_type {
+							Type::Path(ty)
+								if ty.qself.is_none()
+									&& generics
+										.type_params()
+										.any(|param| ty.path.is_ident(&param.ident)) =>
+							{
+								ty.path.get_ident().unwrap().clone()
+							},
+							_ => {
+								return error(
+									&map_attr.path,
+									"'map' requires the argument of the variant to be a generic \
+									 type parameter!",
+								)
+							},
+						}
+					};
+					map_variants.push((variant_ident.clone(), param));
+				}
+
+				let message = flag!(&args, &"message");
+				if message {
+					if let Some(other) = &message_variant {
+						return error(
+							&variant_ident,
+							&format!("Variant '{}' is already marked as 'message'!", other),
+						);
+					}
+					message_variant = Some(variant_ident.clone());
+				}
+
+				// If it's not a pure generic variant, implement from. Variants targeted by
+				// `from_io` and holding an `io::Error` are constructed by the dispatching
+				// `From<std::io::Error>`, message variants by the `From` impls for strings.
+				// Recursive variants, holding a `Box<Self>`, are constructed explicitly.
+				let io_target = is_io_error(&primitive_type)
+					&& from_io.iter().any(|arm| arm.variant == variant_ident);
+				let recursive = is_boxed_self(&primitive_type, &ident);
+				if !skip_single_from
+					&& !io_target && !message
+					&& !recursive && !is_generic_param(&primitive_type, &generics)
+				{
+					let prefer_from = flag!(&args, &"prefer_from");
+					from_structs.push((primitive_type, variant_ident.clone(), prefer_from));
+				}
+
+				if optional {
+					let (display_some, display_none, source_ret) = {
+						if transparent {
+							(
+								quote!(::core::fmt::Display::fmt(x, f)?),
+								quote!(f.write_str(#variant_ident_str)?),
+								quote!(x.as_ref().and_then(::std::error::Error::source)),
+							)
+						} else {
+							outline_display = true;
+							(
+								quote!(display_variant(f, x)?),
+								quote!(display_variant(f, &#variant_ident_str)?),
+								quote!(x
+									.as_ref()
+									.map(|x| x as &(dyn ::std::error::Error + 'static))),
+							)
+						}
+					};
+
+					err_sources.extend(quote! {
+						#cfg Self::#variant_ident(x, ..) => #source_ret,
+					});
+					display_cases.push(quote! {
+						#cfg Self::#variant_ident (x, ..) => {
+							match x {
+								::core::option::Option::Some(x) => #display_some,
+								::core::option::Option::None => #display_none,
+							}
+						}
+					});
+					defmt_cases.push(quote! {
+						#cfg Self::#variant_ident (x, ..) => {
+							defmt::write!(f, "{=str}{=str}: {}", #title, #description, x);
+						}
+					});
+
+					continue;
+				}
+
+				let variant_display;
+
+				let no_source = flag!(&args, &"no_source") || message;
+				if flag!(&args, &"no_source") {
+					no_source_variants.push(variant_ident.clone());
+				}
+				let source_deref = flag!(&args, &"source_deref") || recursive;
+				if source_deref {
+					if let Some(convert_source) = args.get(&"convert_source") {
+						return error(
+							&convert_source.path,
+							"'convert_source' can not be combined with 'source_deref'!",
+						);
+					}
+				}
+
+				// The source of this variant, whose own sources are shown according
+				// to `chain_depth`
+				let mut chain_root = None;
+
+				if !no_source {
+					let src_ret = {
+						if let Some(convert_source_attr) = args.remove(&"convert_source") {
+							let convert = try_compile!(
+								convert_source_attr.parse_value::<syn::Expr>(),
+								|_err| {
+									error(
+										&convert_source_attr.path,
+										"'convert_source' takes exactly one function or closure!",
+									)
+								}
+							);
+
+							match convert {
+								syn::Expr::Path(path) => quote!( #path (x) ),
+								expr => {
+									// The helper gives closures their argument type
+									quote! {{
+										fn convert_source<'a, P: ?::core::marker::Sized, R>(
+											x: &'a P,
+											convert: impl ::core::ops::FnOnce(&'a P) -> R,
+										) -> R {
+											convert(x)
+										}
+										convert_source(x, #expr)
+									}}
+								},
+							}
+						} else {
+							if let Some(display_converted) = args.get(&"display_converted") {
+								return error(
+									&display_converted.path,
+									"'display_converted' requires 'convert_source'!",
+								);
+							}
+
+							if source_deref {
+								quote!(&**x)
+							} else {
+								quote!(x)
+							}
+						}
+					};
+
+					if flag!(&args, &"display_converted") {
+						variant_display = src_ret.clone();
+					} else if source_deref {
+						variant_display = quote!(&**x);
+					} else {
+						variant_display = quote!(x);
+					}
+
+					if transparent {
+						let inner = {
+							if source_deref {
+								quote!(&**x)
+							} else {
+								quote!(x)
+							}
+						};
+						err_sources.extend(quote! {
+							#cfg Self::#variant_ident(x, ..) => ::std::error::Error::source(#inner),
+						});
+						chain_root = Some(inner);
+					} else {
+						err_sources.extend(quote! {
+							#cfg Self::#variant_ident(x, ..) => ::core::option::Option::Some( #src_ret ),
+						});
+						chain_root = Some(src_ret);
+					}
+				} else {
+					if let Some(display_converted) = args.get(&"display_converted") {
+						return error(
+							&display_converted.path,
+							"'display_converted' can not be combined with 'no_source'!",
+						);
+					}
+
+					if message {
+						variant_display = quote!(x);
+					} else {
+						variant_display = quote!(#variant_ident_str);
+					}
+				}
+
+				// `Format` counterpart of the displayed value of this variant
+				let defmt_value = {
+					if message {
+						quote!(::core::convert::AsRef::<str>::as_ref(x))
+					} else if no_source {
+						quote!(#variant_ident_str)
+					} else if source_deref {
+						quote!(&**x)
+					} else {
+						quote!(x)
+					}
+				};
+
+				if transparent && !args.contains_key(&"display_with") {
+					defmt_cases.push(quote! {
+						#cfg Self::#variant_ident (x, ..) => {
+							defmt::Format::format(#defmt_value, f);
+						}
+					});
+				} else {
+					// Custom formatting can not be mirrored, thus the variant is named
+					// instead
+					let defmt_value = {
+						if args.contains_key(&"display_with") {
+							quote!(#variant_ident_str)
+						} else {
+							defmt_value
+						}
+					};
+					defmt_cases.push(quote! {
+						#cfg Self::#variant_ident (x, ..) => {
+							defmt::write!(f, "{=str}{=str}: {}", #title, #description, #defmt_value);
+						}
+					});
+				}
+
+				let show_chain = {
+					match (&chain_depth, &chain_root) {
+						(Some(depth), Some(root)) if !args.contains_key(&"display_with") => {
+							outline_chain = true;
+							quote! {
+								if !f.alternate() {
+									display_chain(f, ::std::error::Error::source(#root), #depth)?;
+								}
+							}
+						},
+						_ => proc_macro2::TokenStream::new(),
+					}
+				};
+				// With the chain shown, the error of this variant is shown by its
+				// alternate form as well
+				let variant_display = {
+					if show_chain.is_empty() {
+						quote!(#variant_display)
+					} else {
+						quote!(::core::format_args!("{:#}", #variant_display))
+					}
+				};
+
+				if let Some(display_with_attr) = args.remove(&"display_with") {
+					let display_with = try_compile!(
+						display_with_attr.parse_value::<syn::Path>(),
+						|_err| {
+							error(
+								&display_with_attr.path,
+								"'display_with' takes exactly one path to a function!",
+							)
+						}
+					);
+
+					display_cases.push(quote! {
+						#cfg Self::#variant_ident (x, ..) => {
+							#display_with(x, f)?;
+						}
+					});
+				} else if transparent {
+					display_cases.push(quote! {
+						#cfg Self::#variant_ident (x, ..) => {
+							::core::fmt::Display::fmt(&#variant_display, f)?;
+							#show_chain
+						}
+					});
+				} else {
+					outline_display = true;
+					display_cases.push(quote! {
+						#cfg Self::#variant_ident (x, ..) => {
+							display_variant(f, &#variant_display)?;
+							#show_chain
+						}
+					});
+				}
+			}
+
+			display_cases.push(quote! {
+				_ => {}
+			});
+			defmt_cases.push(quote! {
+				_ => {}
+			});
+
+			defmt_format = quote! {
+				match self {
+					#(#defmt_cases),*
+				}
+			};
+
+			// The formatting is outlined into a non-generic function, which is thus
+			// shared by all instantiations of generic target types
+			let display_variant = {
+				if outline_display {
+					quote! {
+						fn display_variant(
+							f: &mut ::core::fmt::Formatter<'_>,
+							value: &dyn ::core::fmt::Display,
+						) -> ::core::fmt::Result {
+							if f.alternate() {
+								return ::core::write!(f, "{}{}", #title, #description);
+							}
+							::core::writeln!(f, "{}{}:", #title, #description)?;
+							::core::write!(f, "  └ {}", value)
+						}
+					}
+				} else {
+					proc_macro2::TokenStream::new()
+				}
+			};
+
+			let display_chain = {
+				if outline_chain {
+					display_chain
+				} else {
+					proc_macro2::TokenStream::new()
+				}
+			};
+
+			display = quote! {
+				#display_variant
+				#display_chain
+
+				match self {
+					#(#display_cases),*
+				}
+				::core::result::Result::Ok(())
+			};
+
+			err_source = quote! {
+				match self {
+					#err_sources
+					_ => ::core::option::Option::None
+				}
+			};
+		},
+		Data::Struct(data) if flag!(&toplevel_args, &"transparent") => {
+			let inner_type = {
+				match &data.fields {
+					Fields::Unnamed(fields) if fields.unnamed.len() == 1 => &fields.unnamed[0].ty,
+					_ => {
+						return error(
+							&toplevel_args[&"transparent"].path,
+							"Transparent structs must specify exactly one unnamed field!",
+						)
+					},
+				}
+			};
+
+			display = {
+				match &chain_depth {
+					Some(depth) => {
+						quote! {
+							#display_chain
+
+							::core::write!(f, "{:#}", self.0)?;
+							if f.alternate() {
+								return ::core::result::Result::Ok(());
+							}
+							display_chain(f, ::std::error::Error::source(&self.0), #depth)
+						}
+					},
+					None => quote!(::core::fmt::Display::fmt(&self.0, f)),
+				}
+			};
+
+			defmt_format = quote! {
+				defmt::Format::format(&self.0, f)
+			};
+
+			err_source = quote!(::std::error::Error::source(&self.0));
+
+			if flag!(&toplevel_args, &"deref") {
+				generated.extend(quote! {
+					#[automatically_derived]
+					impl #generics_impl ::core::ops::Deref for #ident #generics_type #generics_where {
+						type Target = #inner_type;
+
+						fn deref(&self) -> &Self::Target {
+							&self.0
+						}
+					}
+
+					#[automatically_derived]
+					impl #generics_impl ::core::convert::AsRef< #inner_type > for #ident #generics_type #generics_where {
+						fn as_ref(&self) -> &#inner_type {
+							&self.0
+						}
+					}
+				});
+			}
+		},
+		Data::Struct(_) => {
+			display = quote! {
+				::core::write!(f, "{}{}", #title, #description)
+			};
+
+			defmt_format = quote! {
+				defmt::write!(f, "{=str}{=str}", #title, #description)
+			};
+
+			err_source = quote!(::core::option::Option::None);
+		},
+		_ => {
+			return error(&original_input, "Can only be used on enums!");
+		},
+	}
+
+	// The conditions of the given variant
+	let cfg_of =
+		|variant_ident: &Ident| variant_cfgs.get(variant_ident).cloned().unwrap_or_default();
+
+	// Constructs the given variant from `value`, capturing the location if
+	// requested
+	let construct = |variant_ident: &Ident, value: proc_macro2::TokenStream| {
+		let value = {
+			if optional_variants.contains(variant_ident) {
+				quote!(::core::option::Option::Some(#value))
+			} else {
+				value
+			}
+		};
+		if location_variants.contains(variant_ident) {
+			quote!( #ident::#variant_ident( #value, ::core::panic::Location::caller() ) )
+		} else {
+			quote!( #ident::#variant_ident( #value ) )
+		}
+	};
+
+	if let Some(result_ext_attr) = toplevel_args.get(&"result_ext") {
+		if !matches!(result_ext_attr.value, AttrValue::Flag) {
+			return error(
+				&result_ext_attr.path,
+				"'result_ext' attribute takes no arguments!",
+			);
+		}
+		if !generics.params.is_empty() {
+			return error(
+				&result_ext_attr.path,
+				"'result_ext' is not supported for generic target types!",
+			);
+		}
+
+		let vis = &original_input.vis;
+		let trait_ident = format_ident!("{}ResultExt", ident);
+		let into_fn = format_ident!("into_{}", to_snake_case(&ident.to_string()));
+		let into_doc = format!("Converts the error of this result into `{}`.", ident);
+
+		let mut or_fns = Vec::new();
+		let mut or_impls = Vec::new();
+		for (variant_ident, payload) in &payload_variants {
+			let or_fn = format_ident!("or_{}", to_snake_case(&variant_ident.to_string()));
+			let or_doc = format!(
+				"Converts the error of this result into `{}::{}`.",
+				ident, variant_ident
+			);
+			let construct = construct(variant_ident, quote!(::core::convert::Into::into(err)));
+			let cfg = cfg_of(variant_ident);
+
+			or_fns.push(quote! {
+				#cfg
+				#[doc = #or_doc]
+				fn #or_fn(self) -> ::core::result::Result<T, #ident>
+				where
+					E: ::core::convert::Into<#payload>;
+			});
+			or_impls.push(quote! {
+				#cfg
+				#[track_caller]
+				fn #or_fn(self) -> ::core::result::Result<T, #ident>
+				where
+					E: ::core::convert::Into<#payload>,
+				{
+					match self {
+						::core::result::Result::Ok(ok) => ::core::result::Result::Ok(ok),
+						::core::result::Result::Err(err) => ::core::result::Result::Err(#construct),
+					}
+				}
+			});
+		}
+
+		let trait_doc = format!("Conversions of the errors of results into `{}`.", ident);
+
+		generated.extend(quote! {
+			#[doc = #trait_doc]
+			#vis trait #trait_ident<T, E> {
+				#[doc = #into_doc]
+				fn #into_fn(self) -> ::core::result::Result<T, #ident>
+				where
+					E: ::core::convert::Into<#ident>;
+
+				#(#or_fns)*
+			}
+
+			#[automatically_derived]
+			impl<T, E> #trait_ident<T, E> for ::core::result::Result<T, E> {
+				#[track_caller]
+				fn #into_fn(self) -> ::core::result::Result<T, #ident>
+				where
+					E: ::core::convert::Into<#ident>,
+				{
+					self.map_err(::core::convert::Into::into)
+				}
+
+				#(#or_impls)*
+			}
+		});
+	}
+
+	if let Some((variant_ident, message_type)) = &context_variant {
+		let cfg = cfg_of(variant_ident);
+		generated.extend(quote! {
+			#cfg
+			impl #generics_impl #ident #generics_type #generics_where {
+				/// Wraps this error, adding the given message as context.
+				pub fn context(self, message: impl ::core::convert::Into<#message_type>) -> Self {
+					Self::#variant_ident(
+						::core::convert::Into::into(message),
+						::std::boxed::Box::new(self),
+					)
+				}
+			}
+		});
+	}
+
+	if let Some(meta_attr) = toplevel_args.get(&"meta") {
+		let parser = |input: ParseStream| {
+			let meta_type = input.parse::<Type>()?;
+			let default = input.parse::<Option<syn::Token![,]>>()?.is_some();
+			if default {
+				let keyword = input.parse::<Ident>()?;
+				if keyword != "default" {
+					return Err(syn::Error::new_spanned(keyword, "expected 'default'"));
+				}
+			}
+			Ok((meta_type, default))
+		};
+		let (meta_type, default) = try_compile!(meta_attr.parse_value_with(parser), |_err| {
+			error(
+				&meta_attr.path,
+				"'meta' takes exactly one type, optionally followed by 'default' as in \
+				 'meta(Type, default)'!",
+			)
+		});
+
+		let vis = &original_input.vis;
+		let wrapper_ident = format_ident!("{}WithMeta", ident);
+		let wrapper_doc = format!(
+			"`{}` along with metadata of type `{}`.",
+			ident,
+			quote!(#meta_type)
+		);
+		let where_clause = &generics.where_clause;
+
+		generated.extend(quote! {
+			#[doc = #wrapper_doc]
+			#[derive(::core::fmt::Debug)]
+			#vis struct #wrapper_ident #generics #where_clause {
+				/// The actual error
+				pub error: #ident #generics_type,
+				/// The metadata attached to the error
+				pub meta: #meta_type,
+			}
+
+			impl #generics_impl #ident #generics_type #generics_where {
+				/// Attaches the given metadata to this error.
+				pub fn with_meta(self, meta: #meta_type) -> #wrapper_ident #generics_type {
+					#wrapper_ident {
+						error: self,
+						meta,
+					}
+				}
+			}
+		});
+
+		if default {
+			generated.extend(quote! {
+				#[automatically_derived]
+				impl #generics_impl ::core::convert::From< #ident #generics_type > for #wrapper_ident #generics_type #generics_where {
+					fn from(error: #ident #generics_type) -> Self {
+						Self {
+							error,
+							meta: ::core::default::Default::default(),
+						}
+					}
+				}
+			});
+		}
+
+		if !skip_display {
+			generated.extend(quote! {
+				#[automatically_derived]
+				impl #generics_impl ::core::fmt::Display for #wrapper_ident #generics_type #generics_where {
+					fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+						::core::fmt::Display::fmt(&self.error, f)
+					}
+				}
+			});
+		}
+
+		if !skip_error {
+			generated.extend(quote! {
+				#[automatically_derived]
+				impl #generics_impl ::std::error::Error for #wrapper_ident #generics_type #generics_where {
+					fn source(&self) -> ::std::option::Option<&(dyn ::std::error::Error + 'static)> {
+						::std::error::Error::source(&self.error)
+					}
+				}
+			});
+		}
+	}
+
+	if let Some(variants_attr) = toplevel_args.get(&"variants") {
+		if !matches!(variants_attr.value, AttrValue::Flag) {
+			return error(
+				&variants_attr.path,
+				"'variants' attribute takes no arguments!",
+			);
+		}
+		if !matches!(original_input.data, Data::Enum(_)) {
+			return error(&variants_attr.path, "'variants' can only be used on enums!");
+		}
+
+		let variant_cfgs = all_variants.iter().map(&cfg_of);
+		let variant_strs = all_variants
+			.iter()
+			.map(|variant_ident| variant_ident.to_string());
+
+		generated.extend(quote! {
+			impl #generics_impl #ident #generics_type #generics_where {
+				/// The names of all variants of this enum.
+				pub const VARIANTS: &'static [&'static str] = &[
+					#( #variant_cfgs #variant_strs, )*
+				];
+
+				/// The number of variants of this enum.
+				pub const VARIANT_COUNT: usize = Self::VARIANTS.len();
+			}
+		});
+	}
+
+	if let Some(mergeable_attr) = toplevel_args.get(&"mergeable") {
+		if !matches!(mergeable_attr.value, AttrValue::Flag) {
+			return error(
+				&mergeable_attr.path,
+				"'mergeable' attribute takes no arguments!",
+			);
+		}
+		if !matches!(original_input.data, Data::Enum(_)) {
+			return error(
+				&mergeable_attr.path,
+				"'mergeable' can only be used on enums!",
+			);
+		}
+		if !generics.params.is_empty() {
+			return error(
+				&mergeable_attr.path,
+				"'mergeable' is not supported for generic target types!",
+			);
+		}
+		if let Some(variant_ident) = all_variants.iter().find(|variant_ident| {
+			!payload_variants
+				.iter()
+				.any(|(payload_ident, _)| payload_ident == *variant_ident)
+		}) {
+			return error(
+				variant_ident,
+				&format!(
+					"'mergeable' requires all variants to wrap an error, which '{}' does not!",
+					variant_ident
+				),
+			);
+		}
+
+		// `merge_errors!` calls the macro with the tokens surrounding the merged
+		// enum, which are passed back along with its variants
+		let macro_ident = format_ident!("__compound_error_variants_of_{}", ident);
+		let variant_cfgs = payload_variants
+			.iter()
+			.map(|(variant_ident, _)| cfg_of(variant_ident));
+		let variant_idents = payload_variants
+			.iter()
+			.map(|(variant_ident, _)| variant_ident);
+		let payloads = payload_variants.iter().map(|(_, payload)| payload);
+
+		generated.extend(quote! {
+			#[doc(hidden)]
+			#[allow(unused_macros)]
+			macro_rules! #macro_ident {
+				({ $($before:tt)* } { $($after:tt)* }) => {
+					#krate::merge_errors! {
+						$($before)* { #( #variant_cfgs #variant_idents(#payloads), )* } $($after)*
+					}
+				};
+			}
+
+			#[doc(hidden)]
+			#[allow(unused_imports)]
+			pub(crate) use #macro_ident as #ident;
+		});
+	}
+
+	if !location_variants.is_empty() {
+		let location_cfgs = location_variants.iter().map(&cfg_of);
+		generated.extend(quote! {
+			impl #generics_impl #ident #generics_type #generics_where {
+				/// Returns the location at which this error was converted into
+				/// its variant, if the variant captures it.
+				pub fn location(&self) -> ::core::option::Option<&'static ::core::panic::Location<'static>> {
+					match self {
+						#(
+							#location_cfgs
+							Self::#location_variants(_, location) => ::core::option::Option::Some(*location),
+						)*
+						#[allow(unreachable_patterns)]
+						_ => ::core::option::Option::None,
+					}
+				}
+			}
+		});
+	}
+
+	// Emits the tracing event for the conversion of `value` into `variant`,
+	// recording `value` by `Debug` for variants without source, whose
+	// arguments need not implement `Display`
+	let trace_from = |variant_ident: &Ident, value: proc_macro2::TokenStream| {
+		if let Some(level) = &trace_level {
+			let variant_ident_str = variant_ident.to_string();
+			let ident_str = ident.to_string();
+			let marker = {
+				if no_source_variants.contains(variant_ident) {
+					quote!(?)
+				} else {
+					quote!(%)
+				}
+			};
+			quote! {
+				#krate::__private::tracing::#level!(
+					variant = #variant_ident_str,
+					error = #marker #value,
+					"error converted into '{}'",
+					#ident_str,
+				);
+			}
+		} else {
+			proc_macro2::TokenStream::new()
+		}
+	};
+
+	// Variants wrapping the same type would result in conflicting `From` impls,
+	// unless all but one of them are marked as `prefer_from`. Types are only
+	// compared textually, as they can not be resolved here.
+	let mut from_clashes: Vec<(String, Vec<(Ident, bool)>)> = Vec::new();
+	for (from_struct, variant_ident, prefer_from) in &from_structs {
+		let cfg = cfg_of(variant_ident);
+		let key = quote!(#cfg #from_struct).to_string();
+		match from_clashes.iter_mut().find(|(other, _)| *other == key) {
+			Some((_, variant_idents)) => variant_idents.push((variant_ident.clone(), *prefer_from)),
+			None => from_clashes.push((key, vec![(variant_ident.clone(), *prefer_from)])),
+		}
+	}
+
+	let mut from_skipped: Vec<Ident> = Vec::new();
+	let mut clash_errors = TokenStream::new();
+	for (_, variant_idents) in &from_clashes {
+		if variant_idents.len() < 2 {
+			continue;
+		}
+
+		let names = {
+			variant_idents
+				.iter()
+				.map(|(variant_ident, _)| format!("'{}'", variant_ident))
+				.collect::<Vec<_>>()
+				.join(", ")
+		};
+		let preferred: Vec<_> = variant_idents
+			.iter()
+			.filter(|(_, prefer)| *prefer)
+			.collect();
+
+		match preferred.as_slice() {
+			[_] => {
+				from_skipped.extend(
+					variant_idents
+						.iter()
+						.filter(|(_, prefer)| !*prefer)
+						.map(|(variant_ident, _)| variant_ident.clone()),
+				);
+			},
+			[] => {
+				for (variant_ident, _) in variant_idents {
+					clash_errors.extend(error(
+						variant_ident,
+						&format!(
+							"The variants {} wrap the same type, thus only one of them can \
+							 implement 'From'! Mark it as 'prefer_from' or the others as \
+							 'skip_single_from'.",
+							names
+						),
+					));
+				}
+			},
+			_ => {
+				for (variant_ident, _) in preferred {
+					clash_errors.extend(error(
+						variant_ident,
+						&format!(
+							"Only one of the variants {} can be marked as 'prefer_from'!",
+							names
+						),
+					));
+				}
+			},
+		}
+	}
+	if !clash_errors.is_empty() {
+		return clash_errors;
+	}
+
+	for (from_struct, variant_ident, _) in from_structs {
+		if from_skipped.contains(&variant_ident) {
+			continue;
+		}
+
+		let trace = trace_from(&variant_ident, quote!(primitive));
+		let construct = construct(&variant_ident, quote!(primitive));
+		let cfg = cfg_of(&variant_ident);
+		let stream = quote! {
+			#cfg
+			#[automatically_derived]
+			impl #generics_impl ::core::convert::From< #from_struct > for #ident #generics_type #generics_where {
+				#[track_caller]
+				#from_attrs
+				fn from(primitive: #from_struct) -> Self {
+					#trace
+					#construct
+				}
+			}
+		};
+
+		generated.extend(stream);
+	}
+
+	if let Some(variant_ident) = &from_infallible {
+		let cfg = cfg_of(variant_ident);
+		generated.extend(quote! {
+			#cfg
+			#[automatically_derived]
+			impl #generics_impl ::core::convert::From< ::core::convert::Infallible > for #ident #generics_type #generics_where {
+				fn from(infallible: ::core::convert::Infallible) -> Self {
+					match infallible {}
+				}
+			}
+		});
+	}
+
+	for (from_enum, variant_idents) in from_enums {
+		let mut cases = proc_macro2::TokenStream::new();
+		let from_enum_path = enum_path(&from_enum);
+
+		// Patterns are spanned to the respective `inline_from` attribute, so that
+		// missing or superfluous variants are reported right there. The bindings
+		// are spanned alike, as the attribute resolves differently from the call
+		// site for enums defined by a macro, e.g. `merge_errors!`.
+		let match_span = variant_idents[0].2;
+		let composite = Ident::new("composite", match_span);
+
+		for (variant_ident, payload_type, span) in variant_idents {
+			let payload = Ident::new("p", span);
+			let trace = trace_from(&variant_ident, quote!(#payload));
+			let construct = construct(&variant_ident, quote!(#payload));
+			let source_variant = Ident::new(&variant_ident.to_string(), span);
+			let mut source_path = from_enum_path.clone();
+			for segment in &mut source_path.segments {
+				segment.ident.set_span(span);
+			}
+			let cfg = cfg_of(&variant_ident);
+			// The typed binding ensures matching payload types, again reported at
+			// the attribute
+			cases.extend(quote_spanned! { span =>
+				#cfg
+				#source_path::#source_variant( #payload, .. ) => {
+					let #payload: #payload_type = #payload;
+					#trace
+					#construct
+				},
+			});
+		}
+
+		// All variants not inlined explicitly, including those added to
+		// `#[non_exhaustive]` enums in the future
+		match from_enum_rest.get(&from_enum) {
+			Some(InlineRest::With(with)) => {
+				cases.extend(quote! {
+					#[allow(unreachable_patterns)]
+					other => (#with)(other),
+				});
+			},
+			Some(InlineRest::Fallback(variant_ident)) => {
+				let value = {
+					if message_variant.as_ref() == Some(variant_ident) {
+						quote!(::core::convert::Into::into(
+							::std::string::ToString::to_string(&other)
+						))
+					} else {
+						quote!(::core::convert::Into::into(other))
+					}
+				};
+				let trace = trace_from(variant_ident, quote!(other));
+				let construct = construct(variant_ident, value);
+				cases.extend(quote! {
+					#[allow(unreachable_patterns)]
+					other => {
+						#trace
+						#construct
+					},
+				});
+			},
+			None => {},
+		}
+
+		let match_composite = quote_spanned! { match_span =>
+			match #composite {
+				#cases
+			}
+		};
+
+		let stream = quote! {
+			#[automatically_derived]
+			impl #generics_impl ::core::convert::From< #from_enum > for #ident #generics_type #generics_where {
+				#[track_caller]
+				#from_attrs
+				fn from(#composite: #from_enum) -> Self {
+					#match_composite
+				}
+			}
+		};
+
+		generated.extend(stream);
+	}
+
+	if !skip_display {
+		let display_with_url = {
+			if flag!(&toplevel_args, &"display_url") {
+				quote! {
+					{ #display }?;
+					if f.alternate() {
+						return ::core::result::Result::Ok(());
+					}
+					if let ::core::option::Option::Some(url) = #krate::CompoundError::url(self) {
+						::core::write!(f, "\n  see {}", url)?;
+					}
+					::core::result::Result::Ok(())
+				}
+			} else {
+				display
+			}
+		};
+
+		generated.extend(quote! {
+			#[automatically_derived]
+			impl #generics_impl ::core::fmt::Display for #ident #generics_type #generics_where {
+				fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+					#display_with_url
+				}
+			}
+		});
+	}
+
+	let description_ret = {
+		match &description_value {
+			Some(value) => quote!(::core::option::Option::Some(#value)),
+			None => quote!(::core::option::Option::None),
+		}
+	};
+
+	let code_default = {
+		match &code {
+			Some(value) => quote!(::core::option::Option::Some(#value)),
+			None => quote!(::core::option::Option::None::<&'static str>),
+		}
+	};
+
+	// Returns the value of the variant of `self`, if given in `values`, or
+	// `default` otherwise
+	let per_variant = |values: &[(Ident, proc_macro2::TokenStream)],
+	                   default: proc_macro2::TokenStream| {
+		if values.is_empty() {
+			return default;
+		}
+
+		let cfgs = values
+			.iter()
+			.map(|(variant_ident, _)| cfg_of(variant_ident));
+		let variants = values.iter().map(|(variant_ident, _)| variant_ident);
+		let values = values.iter().map(|(_, value)| value);
+
+		quote! {
+			match *self {
+				#(
+					#cfgs
+					Self::#variants { .. } => #values,
+				)*
+				#[allow(unreachable_patterns)]
+				_ => #default,
+			}
+		}
+	};
+
+	let variant_name_ret = {
+		if matches!(original_input.data, Data::Enum(_)) {
+			let variant_cfgs = all_variants.iter().map(&cfg_of);
+			let variant_strs = all_variants
+				.iter()
+				.map(|variant_ident| variant_ident.to_string());
+
+			quote! {
+				match *self {
+					#(
+						#variant_cfgs
+						Self::#all_variants { .. } => ::core::option::Option::Some(#variant_strs),
+					)*
+				}
+			}
+		} else {
+			quote!(::core::option::Option::None)
+		}
+	};
+	// Keys default to the type-level key, otherwise to the names of the target
+	// type and the variant
+	let type_key = msg_key.unwrap_or_else(|| to_snake_case(&ident.to_string()));
+	let message_key_ret = {
+		if matches!(original_input.data, Data::Enum(_)) {
+			let variant_cfgs = all_variants.iter().map(&cfg_of);
+			let variant_keys = all_variants.iter().map(|variant_ident| {
+				match variant_msg_keys.get(variant_ident) {
+					Some(key) => key.clone(),
+					None if msg_key_attr.is_some() => type_key.clone(),
+					None => format!("{}.{}", type_key, to_snake_case(&variant_ident.to_string())),
+				}
+			});
+
+			quote! {
+				match *self {
+					#(
+						#variant_cfgs
+						Self::#all_variants { .. } => #variant_keys,
+					)*
+				}
+			}
+		} else {
+			quote!(#type_key)
+		}
+	};
+	if flag!(&toplevel_args, &"catalog") {
+		let option = |value: Option<&String>| {
+			match value {
+				Some(value) => quote!(::core::option::Option::Some(#value)),
+				None => quote!(::core::option::Option::None),
+			}
+		};
+
+		let entries: Vec<_> = {
+			if matches!(original_input.data, Data::Enum(_)) {
+				all_variants
+					.iter()
+					.map(|variant_ident| {
+						let cfg = cfg_of(variant_ident);
+						let variant_str = variant_ident.to_string();
+						// Variants are described by their doc comments
+						let description = option(
+							variant_docs
+								.get(variant_ident)
+								.or(description_value.as_ref()),
+						);
+						let code = {
+							match variant_codes
+								.iter()
+								.find(|(other, _)| other == variant_ident)
+							{
+								Some((_, code)) => code.clone(),
+								None => code_default.clone(),
+							}
+						};
+						let help = option(variant_helps.get(variant_ident).or(help.as_ref()));
+
+						quote! {
+							#cfg
+							#krate::ErrorCatalogEntry {
+								variant: ::core::option::Option::Some(#variant_str),
+								title: #title,
+								description: #description,
+								code: #code,
+								help: #help,
+							},
+						}
+					})
+					.collect()
+			} else {
+				let description = option(description_value.as_ref());
+				let help = option(help.as_ref());
+				vec![quote! {
+					#krate::ErrorCatalogEntry {
+						variant: ::core::option::Option::None,
+						title: #title,
+						description: #description,
+						code: #code_default,
+						help: #help,
+					},
+				}]
+			}
+		};
+
+		generated.extend(quote! {
+			impl #generics_impl #ident #generics_type #generics_where {
+				/// The catalog of all errors represented by this type.
+				pub const CATALOG: &'static [#krate::ErrorCatalogEntry] = &[
+					#(#entries)*
+				];
+			}
+		});
+	}
+
+	if !variant_groups.is_empty() {
+		let vis = &original_input.vis;
+		let group_ident = format_ident!("{}Group", ident);
+		let group_doc = format!("The groups of the variants of `{}`.", ident);
+
+		// The groups in order of their first appearance along with their variants
+		let mut groups: Vec<(Ident, Vec<Ident>)> = Vec::new();
+		for (variant_ident, group) in &variant_groups {
+			match groups.iter_mut().find(|(other, _)| other == group) {
+				Some((_, variants)) => variants.push(variant_ident.clone()),
+				None => groups.push((group.clone(), vec![variant_ident.clone()])),
+			}
+		}
+
+		for (group, variants) in &groups {
+			let subset = subset::Subset {
+				ident: Ident::new(&format!("{}{}", ident, group), group.span()),
+				variants: variants.clone(),
+			};
+			generated.extend(try_compile!(
+				subset::generate(&subset, &original_input, &subset_args, &krate),
+				|err| err.to_compile_error().into()
+			));
+		}
+
+		let group_variants = groups.iter().map(|(group, _)| group);
+		let group_ret = per_variant(
+			&variant_groups
+				.iter()
+				.map(|(variant_ident, group)| {
+					(
+						variant_ident.clone(),
+						quote!(::core::option::Option::Some(#group_ident::#group)),
+					)
+				})
+				.collect::<Vec<_>>(),
+			quote!(::core::option::Option::None),
+		);
+
+		generated.extend(quote! {
+			#[doc = #group_doc]
+			#[derive(
+				::core::fmt::Debug,
+				::core::clone::Clone,
+				::core::marker::Copy,
+				::core::cmp::PartialEq,
+				::core::cmp::Eq,
+				::core::hash::Hash,
+			)]
+			#vis enum #group_ident {
+				#(#group_variants),*
+			}
+
+			impl #generics_impl #ident #generics_type #generics_where {
+				/// Returns the group of this error, if its variant is assigned to
+				/// one.
+				pub fn group(&self) -> ::core::option::Option<#group_ident> {
+					#group_ret
+				}
+			}
+		});
+	}
+
+	if utoipa {
+		let description = {
+			match &description_value {
+				Some(value) => quote!(::core::option::Option::Some(#value)),
+				None => quote!(::core::option::Option::None::<&'static str>),
+			}
+		};
+
+		// The name, code and HTTP status of each variant, or of the target type
+		// itself if it is a struct, along with its cfg
+		let entries: Vec<_> = {
+			if matches!(original_input.data, Data::Enum(_)) {
+				all_variants
+					.iter()
+					.map(|variant_ident| {
+						let code = {
+							match variant_codes
+								.iter()
+								.find(|(other, _)| other == variant_ident)
+							{
+								Some((_, code)) => code.clone(),
+								None => code_default.clone(),
+							}
+						};
+						let status = {
+							match variant_statuses
+								.iter()
+								.find(|(other, _)| other == variant_ident)
+							{
+								Some((_, status)) => *status,
+								None => status,
+							}
+						};
+						(
+							cfg_of(variant_ident),
+							{
+								let variant_str = variant_ident.to_string();
+								quote!(::core::option::Option::Some(#variant_str))
+							},
+							variant_ident.to_string(),
+							code,
+							status,
+						)
+					})
+					.collect()
+			} else {
+				vec![(
+					proc_macro2::TokenStream::new(),
+					quote!(::core::option::Option::None),
+					title.clone(),
+					code_default.clone(),
+					status,
+				)]
+			}
+		};
+		let cfgs: Vec<_> = entries.iter().map(|entry| &entry.0).collect();
+		let variants = entries.iter().map(|entry| &entry.1);
+		let labels = entries.iter().map(|entry| &entry.2);
+		let codes: Vec<_> = entries.iter().map(|entry| &entry.3).collect();
+		let statuses = entries.iter().map(|entry| entry.4);
+		let utoipa_crate = quote!(#krate::__private::utoipa);
+		let http_status_ret = per_variant(
+			&variant_statuses
+				.iter()
+				.map(|(variant_ident, status)| (variant_ident.clone(), quote!(#status)))
+				.collect::<Vec<_>>(),
+			quote!(#status),
+		);
+
+		generated.extend(quote! {
+			#[automatically_derived]
+			impl #generics_impl #utoipa_crate::PartialSchema for #ident #generics_type #generics_where {
+				fn schema() -> #utoipa_crate::openapi::RefOr<#utoipa_crate::openapi::schema::Schema> {
+					let mut variants: ::std::vec::Vec<&'static str> = ::std::vec::Vec::new();
+					let mut codes: ::std::vec::Vec<&'static str> = ::std::vec::Vec::new();
+					#(
+						#cfgs
+						{
+							if let ::core::option::Option::Some(variant) = #variants {
+								variants.push(variant);
+							}
+							if let ::core::option::Option::Some(code) = #codes {
+								codes.push(code);
+							}
+						}
+					)*
+
+					let string = || {
+						#utoipa_crate::openapi::ObjectBuilder::new()
+							.schema_type(#utoipa_crate::openapi::Type::String)
+					};
+					let mut schema = #utoipa_crate::openapi::ObjectBuilder::new()
+						.schema_type(#utoipa_crate::openapi::Type::Object)
+						.title(::core::option::Option::Some(#title))
+						.description(#description)
+						.property("title", string())
+						.required("title")
+						.property("message", string())
+						.required("message");
+					if !variants.is_empty() {
+						schema = schema
+							.property("variant", string().enum_values(::core::option::Option::Some(variants)))
+							.required("variant");
+					}
+					if !codes.is_empty() {
+						schema = schema
+							.property("code", string().enum_values(::core::option::Option::Some(codes)));
+					}
+
+					#utoipa_crate::openapi::RefOr::T(#utoipa_crate::openapi::schema::Schema::Object(schema.build()))
+				}
+			}
+
+			#[automatically_derived]
+			impl #generics_impl #utoipa_crate::ToSchema for #ident #generics_type #generics_where {}
+
+			#[automatically_derived]
+			impl #generics_impl #utoipa_crate::IntoResponses for #ident #generics_type #generics_where {
+				fn responses() -> ::std::collections::BTreeMap<
+					::std::string::String,
+					#utoipa_crate::openapi::RefOr<#utoipa_crate::openapi::response::Response>,
+				> {
+					// The errors described by each HTTP status
+					let mut statuses: ::std::collections::BTreeMap<u16, ::std::vec::Vec<::std::string::String>> =
+						::std::collections::BTreeMap::new();
+					#(
+						#cfgs
+						{
+							let label = match #codes {
+								::core::option::Option::Some(code) => ::std::format!("{} ({})", #labels, code),
+								::core::option::Option::None => ::std::string::ToString::to_string(#labels),
+							};
+							statuses.entry(#statuses).or_default().push(label);
+						}
+					)*
+
+					let schema = #utoipa_crate::openapi::Ref::from_schema_name(<Self as #utoipa_crate::ToSchema>::name());
+					::core::iter::Iterator::collect(::core::iter::Iterator::map(
+						::core::iter::IntoIterator::into_iter(statuses),
+						|(status, labels)| {
+							let response = #utoipa_crate::openapi::ResponseBuilder::new()
+								.description(::std::format!("{}: {}", #title, labels.join(", ")))
+								.content(
+									"application/json",
+									#utoipa_crate::openapi::ContentBuilder::new()
+										.schema(::core::option::Option::Some(::core::clone::Clone::clone(&schema)))
+										.build(),
+								)
+								.build();
+							(::std::string::ToString::to_string(&status), #utoipa_crate::openapi::RefOr::T(response))
+						},
+					))
+				}
+			}
+
+			impl #generics_impl #ident #generics_type #generics_where {
+				/// Returns the HTTP status of this error, as given by the `status`
+				/// attribute.
+				pub fn http_status(&self) -> u16 {
+					#http_status_ret
+				}
+			}
+		});
+	}
+
+	let code_ret = per_variant(&variant_codes, code_default);
+	let url_ret = per_variant(
+		&variant_urls,
+		match &url {
+			Some(url) => quote!(::core::option::Option::Some(#url)),
+			None => quote!(::core::option::Option::None),
+		},
+	);
+	let severity_ret = per_variant(&variant_severities, severity);
+	let category_ret = per_variant(&variant_categories, quote!(#category));
+
+	generated.extend(quote! {
+		#[automatically_derived]
+		impl #generics_impl #krate::CompoundError for #ident #generics_type #generics_where {
+			fn title(&self) -> &'static str {
+				#title
+			}
+
+			fn description(&self) -> ::core::option::Option<&'static str> {
+				#description_ret
+			}
+
+			fn variant_name(&self) -> ::core::option::Option<&'static str> {
+				#variant_name_ret
+			}
+
+			fn code(&self) -> ::core::option::Option<&'static str> {
+				#code_ret
+			}
+
+			fn severity(&self) -> #krate::Severity {
+				#severity_ret
+			}
+
+			fn category(&self) -> &'static str {
+				#category_ret
+			}
+
+			fn message_key(&self) -> &'static str {
+				#message_key_ret
+			}
+
+			fn url(&self) -> ::core::option::Option<&'static str> {
+				#url_ret
+			}
+		}
+	});
+
+	if matches!(original_input.data, Data::Enum(_)) {
+		generated.extend(quote! {
+			impl #generics_impl #ident #generics_type #generics_where {
+				/// Checks whether `self` and `other` are the same variant, ignoring
+				/// their arguments.
+				pub fn same_variant(&self, other: &Self) -> bool {
+					::core::mem::discriminant(self) == ::core::mem::discriminant(other)
+				}
+			}
+		});
+	}
+
+	let discriminants = discriminants(&original_input);
+
+	if let Some((repr, discriminants)) = &discriminants {
+		let cfgs = discriminants
+			.iter()
+			.map(|(variant_ident, _)| cfg_of(variant_ident));
+		let cfgs2 = cfgs.clone();
+		let variants = discriminants.iter().map(|(variant_ident, _)| variant_ident);
+		let variants2 = variants.clone();
+		let values = discriminants.iter().map(|(_, value)| value);
+
+		// The discriminants are mirrored by a fieldless enum, thus implicit ones
+		// are counted by the compiler, honoring the configuration
+		generated.extend(quote! {
+			impl #generics_impl #ident #generics_type #generics_where {
+				/// Returns the discriminant of the variant of this error.
+				pub fn discriminant(&self) -> #repr {
+					#[repr(#repr)]
+					#[allow(dead_code)]
+					enum Discriminant {
+						#(
+							#cfgs
+							#variants #values,
+						)*
+					}
+
+					match *self {
+						#(
+							#cfgs2
+							Self::#variants2 { .. } => Discriminant::#variants2 as #repr,
+						)*
+					}
+				}
+			}
+		});
+	}
+
+	if let Some(ffi_attr) = toplevel_args.get(&"ffi") {
+		if !matches!(ffi_attr.value, AttrValue::Flag) {
+			return error(&ffi_attr.path, "'ffi' attribute takes no arguments!");
+		}
+		if !matches!(original_input.data, Data::Enum(_)) {
+			return error(&ffi_attr.path, "'ffi' can only be used on enums!");
+		}
+
+		let vis = &original_input.vis;
+		let code_ident = format_ident!("{}Code", ident);
+		let code_doc = format!("The C compatible codes of the variants of `{}`.", ident);
+		let variant_cfgs: Vec<_> = all_variants.iter().map(&cfg_of).collect();
+		// Explicit discriminants are kept, otherwise codes start at 1, leaving 0 to
+		// signal success
+		let (repr, codes): (_, Vec<_>) = {
+			match &discriminants {
+				Some((repr, discriminants)) => {
+					(
+						repr.clone(),
+						discriminants
+							.iter()
+							.map(|(_, value)| value.clone())
+							.collect(),
+					)
+				},
+				None => {
+					(
+						format_ident!("C"),
+						(1..=all_variants.len())
+							.map(|code| {
+								let code = proc_macro2::Literal::usize_unsuffixed(code);
+								quote!(= #code)
+							})
+							.collect(),
+					)
+				},
+			}
+		};
+
+		generated.extend(quote! {
+			#[doc = #code_doc]
+			#[repr(#repr)]
+			#[derive(
+				::core::fmt::Debug,
+				::core::clone::Clone,
+				::core::marker::Copy,
+				::core::cmp::PartialEq,
+				::core::cmp::Eq,
+				::core::hash::Hash,
+			)]
+			#vis enum #code_ident {
+				#(
+					#variant_cfgs
+					#all_variants #codes,
+				)*
+			}
+
+			impl #generics_impl #ident #generics_type #generics_where {
+				/// Returns the C compatible code of this error.
+				pub fn ffi_code(&self) -> #code_ident {
+					match *self {
+						#(
+							#variant_cfgs
+							Self::#all_variants { .. } => #code_ident::#all_variants,
+						)*
+					}
+				}
+
+				/// Returns the `Display` output of this error as C string, dropping any
+				/// nul bytes.
+				pub fn message_cstr(&self) -> ::std::ffi::CString {
+					let mut message = ::std::string::ToString::to_string(self);
+					message.retain(|c| c != '\0');
+					::std::ffi::CString::new(message).expect("nul bytes have been removed")
+				}
+			}
+		});
+	}
+
+	if arbitrary {
+		generated.extend(try_compile!(
+			arbitrary::generate(
+				&original_input,
+				&location_variants,
+				from_infallible.as_ref(),
+				&variant_cfgs,
+				&krate
+			),
+			|err| err.to_compile_error().into()
+		));
+	}
+
+	for (variant_ident, param) in &map_variants {
+		generated.extend(try_compile!(
+			map::generate(&original_input, variant_ident, param, &variant_cfgs),
+			|err| err.to_compile_error().into()
+		));
+	}
+
+	if let Some(variant_ident) = &message_variant {
+		let trace = trace_from(variant_ident, quote!(message));
+		let construct_owned =
+			construct(variant_ident, quote!(::core::convert::Into::into(message)));
+		let construct_borrowed = construct(
+			variant_ident,
+			quote!(::core::convert::Into::into(
+				<::std::string::String as ::core::convert::From<&str>>::from(message)
+			)),
+		);
+		let cfg = cfg_of(variant_ident);
+		generated.extend(quote! {
+			#cfg
+			#[automatically_derived]
+			impl #generics_impl ::core::convert::From< ::std::string::String > for #ident #generics_type #generics_where {
+				#[track_caller]
+				#from_attrs
+				fn from(message: ::std::string::String) -> Self {
+					#trace
+					#construct_owned
+				}
+			}
+
+			#cfg
+			#[automatically_derived]
+			impl #generics_impl ::core::convert::From< &str > for #ident #generics_type #generics_where {
+				#[track_caller]
+				#from_attrs
+				fn from(message: &str) -> Self {
+					#trace
+					#construct_borrowed
+				}
+			}
+		});
+	}
+
+	if !from_io.is_empty() {
+		let mut arms = proc_macro2::TokenStream::new();
+		for arm in &from_io {
+			if !payload_variants
+				.iter()
+				.any(|(variant_ident, _)| *variant_ident == arm.variant)
+			{
+				return error(
+					&arm.variant,
+					&format!(
+						"'{}' is no variant of '{}' taking an error!",
+						arm.variant, ident
+					),
+				);
+			}
+
+			let pattern = {
+				match &arm.kind {
+					Some(kind) => quote!(::std::io::ErrorKind::#kind),
+					None => quote!(_),
+				}
+			};
+			let trace = trace_from(&arm.variant, quote!(err));
+			let construct = construct(&arm.variant, quote!(::core::convert::Into::into(err)));
+			let cfg = cfg_of(&arm.variant);
+			arms.extend(quote! {
+				#cfg
+				#pattern => {
+					#trace
+					#construct
+				},
+			});
+		}
+
+		generated.extend(quote! {
+			#[automatically_derived]
+			impl #generics_impl ::core::convert::From< ::std::io::Error > for #ident #generics_type #generics_where {
+				#[track_caller]
+				#from_attrs
+				fn from(err: ::std::io::Error) -> Self {
+					match err.kind() {
+						#arms
+					}
+				}
+			}
+		});
+	}
+
+	if io_kind.is_some() || !variant_io_kinds.is_empty() {
+		let io_kind = io_kind.unwrap_or_else(|| Ident::new("Other", Span::call_site()));
+		let kind_ret = per_variant(&variant_io_kinds, quote!(::std::io::ErrorKind::#io_kind));
+
+		generated.extend(quote! {
+			impl #generics_impl #ident #generics_type #generics_where {
+				/// Returns the kind of `std::io::Error` this error is converted into.
+				pub fn io_kind(&self) -> ::std::io::ErrorKind {
+					#kind_ret
+				}
+			}
+
+			#[automatically_derived]
+			impl #generics_impl ::core::convert::From< #ident #generics_type > for ::std::io::Error #generics_where {
+				fn from(err: #ident #generics_type) -> Self {
+					::std::io::Error::new(err.io_kind(), ::std::string::ToString::to_string(&err))
+				}
+			}
+		});
+	}
+
+	if flag!(&toplevel_args, &"report_fields") {
+		generated.extend(quote! {
+			impl #generics_impl #ident #generics_type #generics_where {
+				/// Returns the fields describing this error for structured error
+				/// reporting, see `compound_error::report_fields()`.
+				pub fn report_fields(&self) -> impl ::core::iter::Iterator<Item = (&'static str, ::std::string::String)> {
+					#krate::report_fields(self)
+				}
+			}
+		});
+	}
+
+	if defmt {
+		// The macros of `defmt` refer to the crate by its name, which thus has to
+		// be in scope
+		generated.extend(quote! {
+			const _: () = {
+				use #krate::__private::defmt;
+
+				#[automatically_derived]
+				impl #generics_impl defmt::Format for #ident #generics_type #generics_where {
+					fn format(&self, f: defmt::Formatter<'_>) {
+						#defmt_format
+					}
+				}
+			};
+		});
+	}
+
+	// BTW: requires `std`
+	if !skip_error {
+		generated.extend(quote! {
+			#[automatically_derived]
+			impl #generics_impl ::std::error::Error for #ident #generics_type #generics_where {
+				fn source(&self) -> ::std::option::Option<&(dyn ::std::error::Error + 'static)> {
+					#err_source
+				}
+			}
+		});
+	}
+
+	generated.into()
+}
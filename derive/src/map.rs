@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+
+use proc_macro2::TokenStream as TokenStream2;
+use proc_macro2::TokenTree;
+use quote::format_ident;
+use quote::quote;
+use syn::Data;
+use syn::DeriveInput;
+use syn::GenericParam;
+use syn::Ident;
+
+use crate::util::bindings;
+use crate::util::to_snake_case;
+
+/// Returns whether `ident` occurs anywhere in `tokens`.
+fn mentions(tokens: TokenStream2, ident: &Ident) -> bool {
+	tokens.into_iter().any(|tt| {
+		match tt {
+			TokenTree::Ident(other) => &other == ident,
+			TokenTree::Group(group) => mentions(group.stream(), ident),
+			_ => false,
+		}
+	})
+}
+
+/// Generates `map_<variant>()`, mapping the payload of `variant_ident`, which
+/// is the generic type parameter `param`, while preserving all other variants.
+pub fn generate(
+	input: &DeriveInput,
+	variant_ident: &Ident,
+	param: &Ident,
+	variant_cfgs: &HashMap<Ident, TokenStream2>,
+) -> syn::Result<TokenStream2> {
+	let data = {
+		match &input.data {
+			Data::Enum(data) => data,
+			_ => unreachable!("'map' is a variant attribute"),
+		}
+	};
+
+	let ident = &input.ident;
+	let (generics_impl, generics_type, generics_where) = input.generics.split_for_impl();
+
+	// The new parameter, avoiding any existing one
+	let mapped = {
+		let taken = |candidate: &Ident| {
+			input.generics.params.iter().any(|param| {
+				match param {
+					GenericParam::Type(ty) => &ty.ident == candidate,
+					GenericParam::Const(cnst) => &cnst.ident == candidate,
+					GenericParam::Lifetime(_) => false,
+				}
+			})
+		};
+		let mut mapped = format_ident!("U");
+		let mut i = 0usize;
+		while taken(&mapped) {
+			i += 1;
+			mapped = format_ident!("U{}", i);
+		}
+		mapped
+	};
+
+	// The arguments of the resulting type along with the bounds of the new
+	// parameter, which are the ones of `param`
+	let mut mapped_args = Vec::new();
+	let mut mapped_bounds = Vec::new();
+	for generic_param in &input.generics.params {
+		match generic_param {
+			GenericParam::Type(ty) if &ty.ident == param => {
+				let bounds = &ty.bounds;
+				if !bounds.is_empty() {
+					mapped_bounds.push(quote!(#mapped: #bounds));
+				}
+				mapped_args.push(quote!(#mapped));
+			},
+			GenericParam::Type(ty) => {
+				let ty = &ty.ident;
+				mapped_args.push(quote!(#ty));
+			},
+			GenericParam::Const(cnst) => {
+				let cnst = &cnst.ident;
+				mapped_args.push(quote!(#cnst));
+			},
+			GenericParam::Lifetime(lifetime) => {
+				let lifetime = &lifetime.lifetime;
+				mapped_args.push(quote!(#lifetime));
+			},
+		}
+	}
+	if let Some(where_clause) = &input.generics.where_clause {
+		for predicate in &where_clause.predicates {
+			if let syn::WherePredicate::Type(predicate) = predicate {
+				let bounds_param = {
+					match &predicate.bounded_ty {
+						syn::Type::Path(ty) => ty.qself.is_none() && ty.path.is_ident(param),
+						_ => false,
+					}
+				};
+				if bounds_param {
+					let bounds = &predicate.bounds;
+					mapped_bounds.push(quote!(#mapped: #bounds));
+				}
+			}
+		}
+	}
+
+	let mut arms = Vec::new();
+	for variant in &data.variants {
+		let other_ident = &variant.ident;
+		let cfg = variant_cfgs.get(other_ident).cloned().unwrap_or_default();
+
+		if other_ident == variant_ident {
+			let rest = (1..variant.fields.len()).map(|i| format_ident!("x{}", i));
+			let rest2 = rest.clone();
+			arms.push(quote! {
+				#cfg
+				Self::#other_ident(x0 #(, #rest)*) => #ident::#other_ident(f(x0) #(, #rest2)*),
+			});
+			continue;
+		}
+
+		let fields = &variant.fields;
+		if mentions(quote!(#fields), param) {
+			return Err(syn::Error::new_spanned(
+				other_ident,
+				format!(
+					"Variant '{}' can not be mapped, because '{}' is also used by variant '{}'!",
+					variant_ident, param, other_ident
+				),
+			));
+		}
+
+		let bindings = bindings(fields);
+		arms.push(quote! {
+			#cfg
+			Self::#other_ident #bindings => #ident::#other_ident #bindings,
+		});
+	}
+
+	let map_fn = format_ident!("map_{}", to_snake_case(&variant_ident.to_string()));
+	let doc = format!(
+		"Maps the argument of `{}::{}` by applying `f`, preserving all other variants.",
+		ident, variant_ident
+	);
+
+	Ok(quote! {
+		impl #generics_impl #ident #generics_type #generics_where {
+			#[doc = #doc]
+			pub fn #map_fn<#mapped>(self, f: impl ::core::ops::FnOnce(#param) -> #mapped) -> #ident<#(#mapped_args),*>
+			where
+				#(#mapped_bounds,)*
+			{
+				match self {
+					#(#arms)*
+				}
+			}
+		}
+	})
+}
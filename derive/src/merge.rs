@@ -0,0 +1,282 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use quote::ToTokens;
+use syn::braced;
+use syn::parenthesized;
+use syn::parse::Parse;
+use syn::parse::ParseStream;
+use syn::punctuated::Punctuated;
+use syn::token;
+use syn::Token;
+
+/// A merged enum as written in `merge_errors! { ... }`.
+struct Merge {
+	attrs: Vec<syn::Attribute>,
+	vis: syn::Visibility,
+	ident: syn::Ident,
+	sources: Punctuated<Source, Token![+]>,
+}
+
+/// One of the enums merged into a [`Merge`], listing its variants. These are
+/// omitted for enums marked as `mergeable`, whose variants are provided by
+/// the hidden macro named like the enum.
+struct Source {
+	path: syn::Path,
+	variants: Option<Punctuated<SourceVariant, Token![,]>>,
+}
+
+/// A variant of a [`Source`], e.g. `Io(std::io::Error)`, along with its `cfg`
+/// attributes.
+struct SourceVariant {
+	attrs: Vec<syn::Attribute>,
+	ident: syn::Ident,
+	ty: syn::Type,
+}
+
+impl Parse for Merge {
+	fn parse(input: ParseStream) -> syn::Result<Self> {
+		let attrs = input.call(syn::Attribute::parse_outer)?;
+		let vis = input.parse()?;
+		input.parse::<Token![enum]>()?;
+		let ident = input.parse()?;
+		input.parse::<Token![=]>()?;
+
+		let mut sources = Punctuated::new();
+		loop {
+			sources.push_value(input.parse()?);
+			if input.peek(Token![;]) {
+				break;
+			}
+			sources.push_punct(input.parse()?);
+		}
+		input.parse::<Token![;]>()?;
+
+		Ok(Self {
+			attrs,
+			vis,
+			ident,
+			sources,
+		})
+	}
+}
+
+impl Parse for Source {
+	fn parse(input: ParseStream) -> syn::Result<Self> {
+		let path: syn::Path = input.parse()?;
+
+		let variants = {
+			if input.peek(token::Brace) {
+				let content;
+				braced!(content in input);
+				Some(content.parse_terminated(SourceVariant::parse)?)
+			} else {
+				if !path
+					.segments
+					.iter()
+					.all(|segment| segment.arguments.is_empty())
+				{
+					return Err(syn::Error::new_spanned(
+						&path,
+						"Generic enums can not be 'mergeable', thus their variants have to be \
+						 listed!",
+					));
+				}
+				None
+			}
+		};
+
+		Ok(Self {
+			path,
+			variants,
+		})
+	}
+}
+
+impl Parse for SourceVariant {
+	fn parse(input: ParseStream) -> syn::Result<Self> {
+		let attrs = input.call(syn::Attribute::parse_outer)?;
+		let ident = input.parse()?;
+
+		let content;
+		parenthesized!(content in input);
+		let ty = content.parse()?;
+
+		Ok(Self {
+			attrs,
+			ident,
+			ty,
+		})
+	}
+}
+
+impl ToTokens for Merge {
+	fn to_tokens(&self, tokens: &mut TokenStream2) {
+		let Self {
+			attrs,
+			vis,
+			ident,
+			sources,
+		} = self;
+		tokens.extend(quote! {
+			#(#attrs)* #vis enum #ident = #sources;
+		});
+	}
+}
+
+impl ToTokens for Source {
+	fn to_tokens(&self, tokens: &mut TokenStream2) {
+		self.path.to_tokens(tokens);
+		if let Some(variants) = &self.variants {
+			tokens.extend(quote!({ #variants }));
+		}
+	}
+}
+
+impl ToTokens for SourceVariant {
+	fn to_tokens(&self, tokens: &mut TokenStream2) {
+		let Self {
+			attrs,
+			ident,
+			ty,
+		} = self;
+		tokens.extend(quote!(#(#attrs)* #ident(#ty)));
+	}
+}
+
+/// A variant of the merged enum along with the enums it is inlined from.
+struct MergedVariant {
+	attrs: Vec<syn::Attribute>,
+	ident: syn::Ident,
+	ty: syn::Type,
+	inline_from: Vec<syn::Path>,
+}
+
+impl Merge {
+	/// Builds the merged enum, where variants sharing the same name across
+	/// the sources are deduplicated.
+	fn into_item(self) -> syn::Result<TokenStream2> {
+		let Self {
+			attrs,
+			vis,
+			ident,
+			sources,
+		} = self;
+
+		let mut merged: Vec<MergedVariant> = Vec::new();
+
+		for source in sources {
+			for variant in source.variants.into_iter().flatten() {
+				if let Some(existing) = merged.iter_mut().find(|v| v.ident == variant.ident) {
+					let existing_ty = &existing.ty;
+					let ty = &variant.ty;
+					if quote!(#existing_ty).to_string() != quote!(#ty).to_string() {
+						return Err(syn::Error::new_spanned(
+							ty,
+							format!(
+								"Variant '{}' is merged with different types!",
+								variant.ident
+							),
+						));
+					}
+
+					existing.inline_from.push(source.path.clone());
+				} else {
+					merged.push(MergedVariant {
+						attrs: variant.attrs,
+						ident: variant.ident,
+						ty: variant.ty,
+						inline_from: vec![source.path.clone()],
+					});
+				}
+			}
+		}
+
+		let variants = merged.iter().map(|variant| {
+			let MergedVariant {
+				attrs,
+				ident,
+				ty,
+				inline_from,
+			} = variant;
+
+			quote! {
+				#(#attrs)*
+				#[compound_error( inline_from( #(#inline_from),* ) )]
+				#ident ( #ty )
+			}
+		});
+
+		Ok(quote! {
+			#(#attrs)*
+			#vis enum #ident {
+				#(#variants),*
+			}
+		})
+	}
+}
+
+/// Expands the `merge_errors! { ... }` macro.
+pub fn expand(input: TokenStream2) -> TokenStream {
+	let parser = |input: ParseStream| {
+		let mut merges = Vec::new();
+		while !input.is_empty() {
+			merges.push(input.parse::<Merge>()?);
+		}
+		Ok(merges)
+	};
+
+	let merges = {
+		match syn::parse::Parser::parse2(parser, input) {
+			Err(err) => return err.to_compile_error().into(),
+			Ok(ok) => ok,
+		}
+	};
+
+	// The variants of the first source not listing them are requested from its
+	// macro, which passes them back to this macro along with all other tokens
+	for (merge_index, merge) in merges.iter().enumerate() {
+		let source_index = merge
+			.sources
+			.iter()
+			.position(|source| source.variants.is_none());
+		if let Some(source_index) = source_index {
+			let Merge {
+				attrs,
+				vis,
+				ident,
+				sources,
+			} = merge;
+			let previous = &merges[..merge_index];
+			let following = &merges[merge_index + 1..];
+			let sources_before = sources.iter().take(source_index);
+			let sources_after = sources.iter().skip(source_index + 1);
+			let path = &sources[source_index].path;
+
+			return quote! {
+				#path! {
+					{ #(#previous)* #(#attrs)* #vis enum #ident = #(#sources_before +)* #path }
+					{ #(+ #sources_after)*; #(#following)* }
+				}
+			}
+			.into();
+		}
+	}
+
+	let mut generated = TokenStream2::new();
+
+	for merge in merges {
+		let derive_input = {
+			match merge.into_item().and_then(syn::parse2) {
+				Err(err) => return err.to_compile_error().into(),
+				Ok(ok) => ok,
+			}
+		};
+
+		let expanded: TokenStream2 =
+			crate::attribute::expand(TokenStream2::new(), derive_input).into();
+		generated.extend(expanded);
+	}
+
+	generated.into()
+}
@@ -0,0 +1,175 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::parse::Parse;
+use syn::parse::ParseStream;
+use syn::punctuated::Punctuated;
+use syn::Data;
+use syn::DeriveInput;
+use syn::Ident;
+use syn::Token;
+
+use crate::util::bindings;
+use crate::util::AttrArg;
+
+/// A subset of the variants of the target type, given as
+/// `subset(Name: VariantA, VariantB, ...)`.
+pub struct Subset {
+	pub ident: Ident,
+	pub variants: Vec<Ident>,
+}
+
+impl Parse for Subset {
+	fn parse(input: ParseStream) -> syn::Result<Self> {
+		let ident = input.parse()?;
+		input.parse::<Token![:]>()?;
+
+		let mut variants = vec![input.parse()?];
+		while input.peek(Token![,]) {
+			input.parse::<Token![,]>()?;
+			if input.is_empty() || input.peek(Token![;]) {
+				break;
+			}
+			variants.push(input.parse()?);
+		}
+
+		Ok(Self {
+			ident,
+			variants,
+		})
+	}
+}
+
+/// Parses the `;` separated subsets given to the `subset` attribute.
+pub fn parse_subsets(attr: &AttrArg) -> syn::Result<Vec<Subset>> {
+	let parser = Punctuated::<Subset, Token![;]>::parse_terminated;
+	attr.parse_value_with(parser)
+		.map(|subsets| subsets.into_iter().collect())
+}
+
+/// Generates the subset enum along with the conversions from and into the
+/// target type.
+///
+/// The subset enum is itself derived as `CompoundError` via `krate` using the
+/// given type-level `forwarded_args`, while the variants keep all their
+/// attributes except for `inline_from` and `group`.
+pub fn generate(
+	subset: &Subset,
+	input: &DeriveInput,
+	forwarded_args: &[TokenStream2],
+	krate: &TokenStream2,
+) -> syn::Result<TokenStream2> {
+	let data = {
+		match &input.data {
+			Data::Enum(data) => data,
+			_ => {
+				return Err(syn::Error::new_spanned(
+					&subset.ident,
+					"Subsets can only be defined for enums!",
+				))
+			},
+		}
+	};
+
+	if !input.generics.params.is_empty() {
+		return Err(syn::Error::new_spanned(
+			&subset.ident,
+			"Subsets of generic enums are not supported!",
+		));
+	}
+
+	let full_ident = &input.ident;
+	let subset_ident = &subset.ident;
+	let vis = &input.vis;
+
+	let mut variants = Vec::new();
+	let mut into_full = Vec::new();
+	let mut from_full = Vec::new();
+	for variant_ident in &subset.variants {
+		let variant = {
+			match data.variants.iter().find(|v| &v.ident == variant_ident) {
+				Some(variant) => variant,
+				None => {
+					return Err(syn::Error::new_spanned(
+						variant_ident,
+						format!("'{}' has no variant '{}'!", full_ident, variant_ident),
+					))
+				},
+			}
+		};
+
+		let mut attrs = Vec::new();
+		for attr in &variant.attrs {
+			if attr.path.is_ident("compound_error") {
+				if attr.tokens.is_empty() {
+					continue;
+				}
+
+				let parser = Punctuated::<AttrArg, Token![,]>::parse_terminated;
+				let args: Vec<_> = attr
+					.parse_args_with(parser)?
+					.into_iter()
+					.filter(|arg| !arg.path.is_ident("inline_from") && !arg.path.is_ident("group"))
+					.collect();
+
+				if !args.is_empty() {
+					attrs.push(quote!( #[compound_error( #(#args),* )] ));
+				}
+			} else {
+				attrs.push(quote!( #attr ));
+			}
+		}
+
+		let cfgs = variant
+			.attrs
+			.iter()
+			.filter(|attr| attr.path.is_ident("cfg"));
+		let cfgs2 = cfgs.clone();
+		let fields = &variant.fields;
+		let bindings = bindings(fields);
+		into_full.push(quote! {
+			#(#cfgs)*
+			#subset_ident::#variant_ident #bindings => Self::#variant_ident #bindings,
+		});
+		from_full.push(quote! {
+			#(#cfgs2)*
+			#full_ident::#variant_ident #bindings => {
+				::core::result::Result::Ok(Self::#variant_ident #bindings)
+			},
+		});
+
+		variants.push(quote! {
+			#(#attrs)*
+			#variant_ident #fields
+		});
+	}
+
+	Ok(quote! {
+		#[derive(::core::fmt::Debug, #krate::CompoundError)]
+		#[compound_error( #(#forwarded_args),* )]
+		#vis enum #subset_ident {
+			#(#variants),*
+		}
+
+		#[automatically_derived]
+		impl ::core::convert::From< #subset_ident > for #full_ident {
+			fn from(subset: #subset_ident) -> Self {
+				match subset {
+					#(#into_full)*
+				}
+			}
+		}
+
+		#[automatically_derived]
+		impl ::core::convert::TryFrom< #full_ident > for #subset_ident {
+			type Error = #full_ident;
+
+			fn try_from(full: #full_ident) -> ::core::result::Result<Self, #full_ident> {
+				match full {
+					#(#from_full)*
+					#[allow(unreachable_patterns)]
+					other => ::core::result::Result::Err(other),
+				}
+			}
+		}
+	})
+}
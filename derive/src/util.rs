@@ -0,0 +1,438 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use proc_macro2::TokenTree;
+use quote::format_ident;
+use quote::quote;
+use quote::quote_spanned;
+use quote::ToTokens;
+use syn::parenthesized;
+use syn::parse::Parse;
+use syn::parse::ParseStream;
+use syn::parse::Parser;
+use syn::punctuated::Punctuated;
+use syn::token;
+use syn::Token;
+
+pub fn error(spanned: &impl syn::spanned::Spanned, message: &str) -> TokenStream {
+	let span = spanned.span();
+	let output = quote_spanned! {
+		span => compile_error!(#message);
+	};
+
+	output.into()
+}
+
+#[derive(Debug, Clone)]
+pub enum AttrArgsError {
+	InvalidArg(syn::Path, Vec<String>),
+	DupliateArg(syn::Path),
+	Syntax(syn::Error),
+}
+
+impl AttrArgsError {
+	pub fn explain(self) -> TokenStream {
+		match self {
+			Self::InvalidArg(key, known_keys) => {
+				let key_str = quote!(#key).to_string().replace(' ', "");
+				let mut message = format!("Invalid argument `{}`.", key_str);
+
+				if let Some(suggestion) = closest_match(&key_str, &known_keys) {
+					message.push_str(&format!(" Did you mean `{}`?", suggestion));
+				}
+				message.push_str(&format!(
+					" Valid arguments here are: {}.",
+					known_keys.join(", ")
+				));
+
+				error(&key, &message)
+			},
+			Self::DupliateArg(key) => error(&key, "Duplicate argument."),
+			Self::Syntax(err) => err.to_compile_error().into(),
+		}
+	}
+}
+
+/// The value given to an attribute argument.
+#[derive(Debug, Clone)]
+pub enum AttrValue {
+	/// `key`
+	Flag,
+	/// `key(...)`, holding the tokens within the parentheses
+	List(TokenStream2),
+	/// `key = ...`, holding the tokens up to the next top-level comma, which is
+	/// not within angle brackets
+	Value(TokenStream2),
+}
+
+#[derive(Debug, Clone)]
+pub struct AttrArg {
+	pub path: syn::Path,
+	pub value: AttrValue,
+}
+
+impl AttrArg {
+	pub fn new(path: syn::Path, value: AttrValue) -> Self {
+		Self {
+			path,
+			value,
+		}
+	}
+
+	/// Parses the comma separated list given as `key(a, b, ...)`. A value
+	/// given as `key = a` is treated as a list with a single element.
+	pub fn parse_list<T: Parse>(&self) -> syn::Result<Vec<T>> {
+		match &self.value {
+			AttrValue::Flag => Ok(Vec::new()),
+			AttrValue::List(tokens) => {
+				let parser = Punctuated::<T, Token![,]>::parse_terminated;
+				Ok(parser.parse2(tokens.clone())?.into_iter().collect())
+			},
+			AttrValue::Value(tokens) => Ok(vec![syn::parse2(tokens.clone())?]),
+		}
+	}
+
+	/// Parses the single value given either as `key = a` or as `key(a)` using
+	/// the given parser.
+	pub fn parse_value_with<P: Parser>(&self, parser: P) -> syn::Result<P::Output> {
+		match &self.value {
+			AttrValue::Flag => Err(syn::Error::new_spanned(&self.path, "Missing value.")),
+			AttrValue::List(tokens) | AttrValue::Value(tokens) => parser.parse2(tokens.clone()),
+		}
+	}
+
+	/// Parses the single value given either as `key = a` or as `key(a)`.
+	pub fn parse_value<T: Parse>(&self) -> syn::Result<T> {
+		match &self.value {
+			AttrValue::Flag => Err(syn::Error::new_spanned(&self.path, "Missing value.")),
+			AttrValue::List(tokens) | AttrValue::Value(tokens) => syn::parse2(tokens.clone()),
+		}
+	}
+}
+
+impl ToTokens for AttrArg {
+	fn to_tokens(&self, tokens: &mut TokenStream2) {
+		self.path.to_tokens(tokens);
+
+		match &self.value {
+			AttrValue::Flag => {},
+			AttrValue::List(list) => tokens.extend(quote!( ( #list ) )),
+			AttrValue::Value(value) => tokens.extend(quote!( = #value )),
+		}
+	}
+}
+
+impl Parse for AttrArg {
+	fn parse(input: ParseStream) -> syn::Result<Self> {
+		let path = input.call(syn::Path::parse_mod_style)?;
+
+		let value = {
+			if input.peek(token::Paren) {
+				let content;
+				parenthesized!(content in input);
+				AttrValue::List(content.parse()?)
+			} else if input.peek(Token![=]) {
+				input.parse::<Token![=]>()?;
+
+				// Commas within angle brackets belong to the value, as in
+				// `HashMap<String, u32>`
+				let mut tokens = TokenStream2::new();
+				let mut depth = 0usize;
+				while !input.is_empty() {
+					if depth == 0 && input.peek(Token![,]) {
+						break;
+					}
+
+					if input.peek(Token![->]) {
+						tokens.extend(input.parse::<Token![->]>()?.into_token_stream());
+						continue;
+					}
+
+					let tt = input.parse::<TokenTree>()?;
+					if let TokenTree::Punct(punct) = &tt {
+						match punct.as_char() {
+							'<' => depth += 1,
+							'>' => depth = depth.saturating_sub(1),
+							_ => {},
+						}
+					}
+					tokens.extend(std::iter::once(tt));
+				}
+				if tokens.is_empty() {
+					return Err(input.error("Expected a value."));
+				}
+
+				AttrValue::Value(tokens)
+			} else {
+				AttrValue::Flag
+			}
+		};
+
+		Ok(Self::new(path, value))
+	}
+}
+
+pub fn attr_args<'ident, I>(
+	attrs: &[syn::Attribute],
+	required_key: &'ident I,
+	known_arg_keys: &[&'ident I],
+) -> Result<HashMap<&'ident I, AttrArg>, AttrArgsError>
+where
+	syn::Ident: PartialEq<I>,
+	I: ?Sized + Hash + Eq + AsRef<str>,
+{
+	let invalid_arg = |path: syn::Path| {
+		let known_keys = known_arg_keys
+			.iter()
+			.map(|key| key.as_ref().to_string())
+			.collect();
+		AttrArgsError::InvalidArg(path, known_keys)
+	};
+
+	let mut args: HashMap<&'ident I, AttrArg> = HashMap::new();
+
+	for attr in attrs {
+		if let Some(specified_key) = attr.path.get_ident() {
+			if specified_key != required_key {
+				continue;
+			}
+		} else {
+			continue;
+		}
+
+		if attr.tokens.is_empty() {
+			// no arguments
+			continue;
+		}
+
+		let parser = Punctuated::<AttrArg, Token![,]>::parse_terminated;
+		let parsed = attr.parse_args_with(parser).map_err(AttrArgsError::Syntax)?;
+
+		for arg in parsed {
+			let known_arg_key = {
+				if let Some(path_ident) = arg.path.get_ident() {
+					if let Some(known_arg_key) = known_arg_keys.iter().find(|arg| path_ident == arg)
+					{
+						known_arg_key
+					} else {
+						return Err(invalid_arg(arg.path));
+					}
+				} else {
+					return Err(invalid_arg(arg.path));
+				}
+			};
+
+			if args.contains_key(known_arg_key) {
+				return Err(AttrArgsError::DupliateArg(arg.path));
+			}
+
+			args.insert(known_arg_key, arg);
+		}
+	}
+
+	Ok(args)
+}
+
+pub fn flag<'a, I: ?Sized + Eq + Hash>(
+	args: &'a HashMap<&I, AttrArg>,
+	ident: &I,
+) -> Result<bool, &'a syn::Path> {
+	if let Some(skip) = args.get(ident) {
+		if !matches!(skip.value, AttrValue::Flag) {
+			return Err(&skip.path);
+		}
+
+		Ok(true)
+	} else {
+		Ok(false)
+	}
+}
+
+/// Returns the bindings of all fields of `fields`, usable both as pattern and
+/// as constructor, e.g. `(x0, x1)` or `{ a, b }`.
+pub fn bindings(fields: &syn::Fields) -> TokenStream2 {
+	match fields {
+		syn::Fields::Named(fields) => {
+			let idents = fields.named.iter().map(|field| &field.ident);
+			quote!({ #(#idents),* })
+		},
+		syn::Fields::Unnamed(fields) => {
+			let idents = (0..fields.unnamed.len()).map(|i| format_ident!("x{}", i));
+			quote!(( #(#idents),* ))
+		},
+		syn::Fields::Unit => TokenStream2::new(),
+	}
+}
+
+/// Checks whether `ty` is nothing but one of the generic parameters (type or
+/// const) declared in `generics`.
+pub fn is_generic_param(ty: &syn::Type, generics: &syn::Generics) -> bool {
+	let path = {
+		match ty {
+			syn::Type::Path(syn::TypePath {
+				qself: None,
+				path,
+			}) => path,
+			_ => return false,
+		}
+	};
+
+	generics.params.iter().any(|param| {
+		match param {
+			syn::GenericParam::Type(ty) => path.is_ident(&ty.ident),
+			syn::GenericParam::Const(cnst) => path.is_ident(&cnst.ident),
+			syn::GenericParam::Lifetime(_) => false,
+		}
+	})
+}
+
+/// Returns the type `T` if `ty` is of the form `Option<T>`.
+pub fn option_inner(ty: &syn::Type) -> Option<&syn::Type> {
+	let segment = {
+		match ty {
+			syn::Type::Path(syn::TypePath {
+				qself: None,
+				path,
+			}) => path.segments.last()?,
+			_ => return None,
+		}
+	};
+	if segment.ident != "Option" {
+		return None;
+	}
+
+	match &segment.arguments {
+		syn::PathArguments::AngleBracketed(args) if args.args.len() == 1 => {
+			match &args.args[0] {
+				syn::GenericArgument::Type(inner) => Some(inner),
+				_ => None,
+			}
+		},
+		_ => None,
+	}
+}
+
+/// Checks whether `ty` is of the form `Box<Self>` or `Box<Target>`, where
+/// `Target` is the type named `ident`, regardless of its generic arguments.
+pub fn is_boxed_self(ty: &syn::Type, ident: &syn::Ident) -> bool {
+	let segment = {
+		match ty {
+			syn::Type::Path(syn::TypePath {
+				qself: None,
+				path,
+			}) => {
+				match path.segments.last() {
+					Some(segment) => segment,
+					None => return false,
+				}
+			},
+			_ => return false,
+		}
+	};
+	if segment.ident != "Box" {
+		return false;
+	}
+
+	match &segment.arguments {
+		syn::PathArguments::AngleBracketed(args) if args.args.len() == 1 => {
+			match &args.args[0] {
+				syn::GenericArgument::Type(syn::Type::Path(syn::TypePath {
+					qself: None,
+					path,
+				})) => {
+					path.segments.last().is_some_and(|inner| {
+						inner.ident == "Self" || (path.segments.len() == 1 && inner.ident == *ident)
+					})
+				},
+				_ => false,
+			}
+		},
+		_ => false,
+	}
+}
+
+/// Checks whether `ty` names `std::io::Error`, i.e. is a path ending in
+/// `io::Error`.
+pub fn is_io_error(ty: &syn::Type) -> bool {
+	match ty {
+		syn::Type::Path(syn::TypePath {
+			qself: None,
+			path,
+		}) => {
+			let mut segments = path.segments.iter().rev();
+			matches!(
+				(segments.next(), segments.next()),
+				(Some(last), Some(module)) if last.ident == "Error" && module.ident == "io"
+			)
+		},
+		_ => false,
+	}
+}
+
+/// Converts an identifier in `CamelCase` into `snake_case`, keeping acronyms
+/// together (e.g. `IOError` becomes `io_error`).
+pub fn to_snake_case(ident: &str) -> String {
+	let chars: Vec<char> = ident.chars().collect();
+	let mut snake = String::new();
+
+	for (i, &ch) in chars.iter().enumerate() {
+		if ch.is_uppercase() && i > 0 {
+			let prev = chars[i - 1];
+			let next_lower = chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+
+			if prev != '_' && (!prev.is_uppercase() || next_lower) {
+				snake.push('_');
+			}
+		}
+
+		snake.extend(ch.to_lowercase());
+	}
+
+	snake
+}
+
+/// Converts a name in `snake_case` or `kebab-case` into `CamelCase` (e.g.
+/// `data_store` becomes `DataStore`).
+pub fn to_camel_case(name: &str) -> String {
+	name.split(['_', '-'])
+		.flat_map(|word| {
+			let mut chars = word.chars();
+			chars
+				.next()
+				.into_iter()
+				.flat_map(char::to_uppercase)
+				.chain(chars)
+		})
+		.collect()
+}
+
+/// Finds the candidate closest to `key`, if it is close enough to be a typo.
+fn closest_match<'a>(key: &str, candidates: &'a [String]) -> Option<&'a str> {
+	candidates
+		.iter()
+		.map(|candidate| (edit_distance(key, candidate), candidate))
+		.filter(|(distance, candidate)| *distance <= (candidate.len() / 3).max(1))
+		.min_by_key(|(distance, _)| *distance)
+		.map(|(_, candidate)| candidate.as_str())
+}
+
+/// Computes the Levenshtein distance between `a` and `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+	let b: Vec<char> = b.chars().collect();
+	let mut distances: Vec<usize> = (0..=b.len()).collect();
+
+	for (i, a_char) in a.chars().enumerate() {
+		let mut diagonal = distances[0];
+		distances[0] = i + 1;
+
+		for (j, b_char) in b.iter().enumerate() {
+			let substitution = diagonal + usize::from(a_char != *b_char);
+			diagonal = distances[j + 1];
+			distances[j + 1] = substitution.min(distances[j] + 1).min(diagonal + 1);
+		}
+	}
+
+	distances[b.len()]
+}
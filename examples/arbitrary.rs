@@ -0,0 +1,46 @@
+use arbitrary::Arbitrary;
+use arbitrary::Unstructured;
+use compound_error::CompoundError;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Timeout", arbitrary)]
+pub struct Timeout {
+	pub millis: u32,
+}
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Refused", arbitrary)]
+pub struct Refused;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Request Error", arbitrary)]
+pub enum RequestError {
+	Timeout(Timeout),
+	Refused(Refused),
+	#[compound_error(message)]
+	Other(String),
+	Retried(Box<RequestError>),
+}
+
+#[compound_error::compound_error(title = "Sync Error", arbitrary)]
+pub enum SyncError<E: std::error::Error + 'static> {
+	#[compound_error(location)]
+	Request(RequestError),
+	Storage(E),
+}
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Disk Full", arbitrary)]
+pub struct DiskFull;
+
+fn main() {
+	let data: Vec<u8> = (0u8..64).map(|i| i.wrapping_mul(37)).collect();
+	let mut u = Unstructured::new(&data);
+
+	while !u.is_empty() {
+		match SyncError::<DiskFull>::arbitrary(&mut u) {
+			Ok(e) => println!("Error: {}", e),
+			Err(e) => println!("Failed to generate an error: {}", e),
+		}
+	}
+}
@@ -0,0 +1,36 @@
+use compound_error::CompoundError;
+
+#[derive(Debug, CompoundError)]
+pub struct Foo;
+
+#[derive(Debug, CompoundError)]
+pub struct Bar;
+
+#[compound_error::compound_error]
+pub enum CompoundFoo {
+	Foo(Foo),
+	Bar(Bar),
+}
+
+#[compound_error::compound_error(title = "Example Error")]
+pub enum ExampleError {
+	#[compound_error(inline_from(CompoundFoo))]
+	Foo(Foo),
+	#[compound_error(inline_from(CompoundFoo))]
+	Bar(Bar),
+}
+
+pub fn throws_compound_foo() -> Result<(), CompoundFoo> {
+	Err(Bar)?
+}
+
+pub fn throws_example() -> Result<(), ExampleError> {
+	Ok(throws_compound_foo()?)
+}
+
+fn main() {
+	if let Err(e) = throws_example() {
+		println!("Error: {}", e);
+		println!("Debug: {:?}", e);
+	}
+}
@@ -0,0 +1,52 @@
+use compound_error::CompoundError;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(
+	title = "Quota Exceeded",
+	code = "Q001",
+	help = "Upgrade your plan.",
+	catalog
+)]
+pub struct QuotaExceeded;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Not Found")]
+pub struct NotFound;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(
+	title = "Upload Error",
+	description = "the upload failed",
+	code = "U000",
+	catalog
+)]
+pub enum UploadError {
+	/// The storage quota of the account is used up.
+	#[compound_error(code = "U001", help = "Delete some files or upgrade your plan.")]
+	QuotaExceeded(QuotaExceeded),
+	/// The target folder does not exist (anymore).
+	NotFound(NotFound),
+	Other(std::io::Error),
+}
+
+fn main() {
+	println!("# Error Reference");
+	for entry in QuotaExceeded::CATALOG.iter().chain(UploadError::CATALOG) {
+		println!();
+		println!(
+			"## {} {}{}",
+			entry.code.unwrap_or("-"),
+			entry.title,
+			entry
+				.variant
+				.map(|variant| format!(" ({})", variant))
+				.unwrap_or_default()
+		);
+		if let Some(description) = entry.description {
+			println!("{}", description);
+		}
+		if let Some(help) = entry.help {
+			println!("Help: {}", help);
+		}
+	}
+}
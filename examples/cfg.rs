@@ -0,0 +1,37 @@
+use compound_error::CompoundError;
+
+#[derive(Debug, CompoundError)]
+pub struct Foo;
+
+#[derive(Debug, CompoundError)]
+pub struct Traced;
+
+#[compound_error::compound_error]
+pub enum ExampleError {
+	Foo(Foo),
+	// This variant only exists if the `tracing` feature is enabled
+	#[compound_error(cfg(feature = "tracing"))]
+	Traced(Traced),
+}
+
+pub fn throws_foo() -> Result<(), Foo> {
+	Err(Foo)
+}
+
+#[cfg(feature = "tracing")]
+pub fn throws_traced() -> Result<(), Traced> {
+	Err(Traced)
+}
+
+pub fn throws_example() -> Result<(), ExampleError> {
+	#[cfg(feature = "tracing")]
+	throws_traced()?;
+
+	Ok(throws_foo()?)
+}
+
+fn main() {
+	if let Err(e) = throws_example() {
+		println!("Error: {}", e);
+	}
+}
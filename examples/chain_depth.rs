@@ -0,0 +1,80 @@
+use std::error::Error;
+use std::fmt;
+
+use compound_error::CompoundError;
+
+/// Errors in the usual style, whose `Display` does not include their source
+#[derive(Debug)]
+pub struct CertificateExpired;
+
+impl fmt::Display for CertificateExpired {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "certificate expired")
+	}
+}
+
+impl Error for CertificateExpired {}
+
+#[derive(Debug)]
+pub struct TlsError(CertificateExpired);
+
+impl fmt::Display for TlsError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "TLS handshake failed")
+	}
+}
+
+impl Error for TlsError {
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		Some(&self.0)
+	}
+}
+
+#[derive(Debug)]
+pub struct ConnectError(TlsError);
+
+impl fmt::Display for ConnectError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "could not connect")
+	}
+}
+
+impl Error for ConnectError {
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		Some(&self.0)
+	}
+}
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Sync Error")]
+pub enum SyncError {
+	Connect(ConnectError),
+}
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Sync Error", chain_depth = 1)]
+pub enum VerboseSyncError {
+	Connect(ConnectError),
+}
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Sync Error", chain_depth = *)]
+pub enum DebugSyncError {
+	Connect(ConnectError),
+}
+
+fn connect() -> Result<(), ConnectError> {
+	Err(ConnectError(TlsError(CertificateExpired)))
+}
+
+fn main() {
+	if let Err(e) = connect() {
+		println!("Error: {}", SyncError::from(e));
+	}
+	if let Err(e) = connect() {
+		println!("Error: {}", VerboseSyncError::from(e));
+	}
+	if let Err(e) = connect() {
+		println!("Error: {}", DebugSyncError::from(e));
+	}
+}
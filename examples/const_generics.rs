@@ -0,0 +1,36 @@
+use compound_error::CompoundError;
+
+#[derive(Debug, CompoundError)]
+pub struct Closed;
+
+#[derive(Debug, CompoundError)]
+pub struct Full<const N: usize>;
+
+#[derive(Debug, CompoundError)]
+pub enum BufferError<const N: usize> {
+	Closed(Closed),
+	Full(Full<N>),
+	#[compound_error(no_source)]
+	Overflow([u8; N]),
+}
+
+pub fn push<const N: usize>(closed: bool, len: usize) -> Result<(), BufferError<N>> {
+	if closed {
+		Err(Closed)?
+	} else if len == N {
+		Err(Full)?
+	} else if len > N {
+		Err([0; N])?
+	} else {
+		Ok(())
+	}
+}
+
+fn main() {
+	if let Err(e) = push::<4>(false, 4) {
+		println!("Error: {}", e);
+	}
+	if let Err(e) = push::<4>(false, 5) {
+		println!("Error: {}", e);
+	}
+}
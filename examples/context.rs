@@ -0,0 +1,37 @@
+use compound_error::CompoundError;
+
+#[derive(Debug, CompoundError)]
+pub struct Foo;
+
+#[derive(Debug, CompoundError)]
+pub struct Bar;
+
+#[derive(Debug, CompoundError)]
+pub enum DerivedError {
+	Foo(Foo),
+	#[compound_error(context)]
+	WithContext(String, Box<DerivedError>),
+}
+
+#[compound_error::compound_error(context)]
+pub enum ExampleError {
+	Foo(Foo),
+	Bar(Bar),
+}
+
+pub fn throws_derived() -> Result<(), DerivedError> {
+	Err(DerivedError::from(Foo).context("while loading the configuration"))
+}
+
+pub fn throws_example() -> Result<(), ExampleError> {
+	Err(ExampleError::from(Bar).context("while saving"))
+}
+
+fn main() {
+	if let Err(e) = throws_derived() {
+		println!("Error: {}", e);
+	}
+	if let Err(e) = throws_example() {
+		println!("Error: {}", e);
+	}
+}
@@ -0,0 +1,49 @@
+use std::sync::Arc;
+
+use compound_error::CompoundError;
+
+#[derive(Debug, CompoundError)]
+pub struct Foo;
+
+#[derive(Debug, CompoundError)]
+pub struct Bar;
+
+#[derive(Debug)]
+pub struct Shared(Arc<Bar>);
+
+impl Shared {
+	pub fn inner(&self) -> &Bar {
+		&self.0
+	}
+}
+
+impl std::fmt::Display for Shared {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "Shared({})", self.0)
+	}
+}
+
+#[derive(Debug, CompoundError)]
+pub enum ExampleError {
+	#[compound_error(convert_source(|x| &**x))]
+	Foo(Box<Foo>),
+	#[compound_error(convert_source(|x| x.inner()), display_converted)]
+	Bar(Shared),
+}
+
+pub fn throws_example(which: u8) -> Result<(), ExampleError> {
+	if which == 0 {
+		Ok(())
+	} else if which == 1 {
+		Err(Box::new(Foo))?
+	} else {
+		Err(Shared(Arc::new(Bar)))?
+	}
+}
+
+fn main() {
+	if let Err(e) = throws_example(2) {
+		println!("Error: {}", e);
+		println!("Source: {:?}", std::error::Error::source(&e));
+	}
+}
@@ -0,0 +1,41 @@
+use compound_error::compound_errors;
+use compound_error::CompoundError;
+
+#[derive(Debug, CompoundError)]
+pub struct Foo;
+
+#[derive(Debug, CompoundError)]
+pub struct Bar;
+
+#[derive(Debug)]
+pub struct Timeout;
+
+compound_errors! {
+	pub enum LowLevel {
+		Foo(Foo),
+		Bar(Bar),
+	}
+
+	/// Errors of the example API.
+	#[compound_error(title = "API Error")]
+	pub enum ApiError {
+		Foo(Foo) inline_from(LowLevel),
+		Bar(Bar) inline_from(LowLevel),
+		Timeout(Timeout) no_source,
+	}
+}
+
+pub fn throws_low_level() -> Result<(), LowLevel> {
+	Err(Foo)?
+}
+
+pub fn throws_api() -> Result<(), ApiError> {
+	Ok(throws_low_level()?)
+}
+
+fn main() {
+	if let Err(e) = throws_api() {
+		println!("Error: {}", e);
+	}
+	println!("Error: {}", ApiError::from(Timeout));
+}
@@ -0,0 +1,69 @@
+use compound_error::CompoundError;
+
+#[derive(Debug, CompoundError, defmt::Format)]
+#[compound_error(title = "Sensor Timeout")]
+pub struct SensorTimeout;
+
+#[derive(Debug, CompoundError, defmt::Format)]
+#[compound_error(title = "Bus Fault", description = "the bus is stuck")]
+pub struct BusFault;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Measure Error", defmt)]
+pub enum MeasureError {
+	SensorTimeout(SensorTimeout),
+	#[compound_error(transparent)]
+	BusFault(BusFault),
+}
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Invalid Reading", defmt)]
+pub struct InvalidReading;
+
+pub fn measure(bus_ok: bool) -> Result<u16, MeasureError> {
+	if bus_ok {
+		Err(SensorTimeout)?
+	} else {
+		Err(BusFault)?
+	}
+}
+
+/// A logger discarding everything, standing in for an actual transport on the
+/// target
+#[defmt::global_logger]
+struct Logger;
+
+unsafe impl defmt::Logger for Logger {
+	fn acquire() {}
+
+	unsafe fn flush() {}
+
+	unsafe fn release() {}
+
+	unsafe fn write(_bytes: &[u8]) {}
+}
+
+defmt::timestamp!("{=u32}", 0);
+
+#[defmt::panic_handler]
+fn panic() -> ! {
+	panic!()
+}
+
+/// Logs the given failure via `defmt`
+pub fn log_failure(e: &MeasureError) {
+	defmt::error!("measuring failed: {}", e);
+	defmt::warn!("{}", InvalidReading);
+}
+
+fn main() {
+	if let Err(e) = measure(false) {
+		// `defmt` can only format on a target with a matching transport and
+		// decoder, thus the host falls back to `Display`
+		if cfg!(target_os = "none") {
+			log_failure(&e);
+		} else {
+			println!("measuring failed: {}", e);
+		}
+	}
+}
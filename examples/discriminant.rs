@@ -0,0 +1,41 @@
+use compound_error::CompoundError;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Not Found")]
+pub struct NotFound;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Timeout")]
+pub struct Timeout;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Refused")]
+pub struct Refused;
+
+/// The discriminants are persisted, thus they must never change
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Request Error", ffi)]
+#[repr(u8)]
+pub enum RequestError {
+	NotFound(NotFound) = 10,
+	Timeout(Timeout) = 20,
+	Refused(Refused),
+}
+
+fn main() {
+	let errors = [
+		RequestError::from(NotFound),
+		RequestError::from(Timeout),
+		RequestError::from(Refused),
+	];
+
+	for err in &errors {
+		println!(
+			"{} = {} ({:?})",
+			err.discriminant(),
+			err.ffi_code() as u8,
+			err
+		);
+	}
+	assert_eq!(errors[2].discriminant(), 21);
+}
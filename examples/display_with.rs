@@ -0,0 +1,44 @@
+use std::fmt;
+
+use compound_error::CompoundError;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Timeout")]
+pub struct Timeout;
+
+#[derive(Debug)]
+pub struct Status(pub u16);
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Request Error")]
+pub enum RequestError {
+	#[compound_error(display_with = display_timeout)]
+	Timeout(Timeout),
+	#[compound_error(display_with = display_status, no_source)]
+	Status(Status),
+}
+
+fn display_timeout(err: &Timeout, f: &mut fmt::Formatter) -> fmt::Result {
+	write!(f, "Timeout happened: {}", err)
+}
+
+fn display_status(status: &Status, f: &mut fmt::Formatter) -> fmt::Result {
+	write!(f, "Unexpected status {}", status.0)
+}
+
+pub fn request(status: u16) -> Result<(), RequestError> {
+	match status {
+		0 => Err(Timeout)?,
+		200 => Ok(()),
+		status => Err(RequestError::Status(Status(status))),
+	}
+}
+
+fn main() {
+	if let Err(e) = request(0) {
+		println!("Error: {}", e);
+	}
+	if let Err(e) = request(404) {
+		println!("Error: {}", e);
+	}
+}
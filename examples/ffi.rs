@@ -0,0 +1,63 @@
+use std::ffi::CStr;
+use std::os::raw::c_char;
+
+use compound_error::CompoundError;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Invalid Handle")]
+pub struct InvalidHandle;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Busy")]
+pub struct Busy;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Device Error", ffi)]
+pub enum DeviceError {
+	InvalidHandle(InvalidHandle),
+	Busy(Busy),
+}
+
+thread_local! {
+	static LAST_ERROR: std::cell::RefCell<Option<std::ffi::CString>> = Default::default();
+}
+
+fn open_device(handle: u32) -> Result<(), DeviceError> {
+	match handle {
+		0 => Err(InvalidHandle)?,
+		1 => Err(Busy)?,
+		_ => Ok(()),
+	}
+}
+
+/// The C API, returning `0` on success or the code of the error
+pub extern "C" fn device_open(handle: u32) -> u32 {
+	match open_device(handle) {
+		Ok(()) => 0,
+		Err(err) => {
+			let code = err.ffi_code() as u32;
+			LAST_ERROR.with(|last| *last.borrow_mut() = Some(err.message_cstr()));
+			code
+		},
+	}
+}
+
+/// Returns the message of the last error, valid until the next call
+pub extern "C" fn device_last_error() -> *const c_char {
+	LAST_ERROR.with(|last| {
+		last.borrow()
+			.as_ref()
+			.map_or(std::ptr::null(), |message| message.as_ptr())
+	})
+}
+
+fn main() {
+	for handle in 0..3 {
+		let code = device_open(handle);
+		if code != 0 {
+			let message = unsafe { CStr::from_ptr(device_last_error()) };
+			println!("{} ({:?})", code, message);
+		}
+	}
+	assert_eq!(DeviceErrorCode::Busy as u32, 2);
+}
@@ -0,0 +1,51 @@
+use compound_error::CompoundError;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Timeout")]
+pub struct Timeout;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Not Found")]
+pub struct NotFound;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Fetch Error")]
+pub enum FetchError<T: std::fmt::Debug> {
+	Timeout(Timeout),
+	NotFound(NotFound),
+	#[compound_error(no_source)]
+	Other(T),
+}
+
+/// Generic types are given to `inline_from` directly, both with and without
+/// the turbofish.
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Sync Error")]
+pub enum SyncError<T: std::fmt::Debug> {
+	#[compound_error(inline_from(FetchError<T>))]
+	Timeout(Timeout),
+	#[compound_error(inline_from(FetchError::<T>))]
+	NotFound(NotFound),
+	#[compound_error(inline_from(FetchError<T>), no_source)]
+	Other(T),
+}
+
+pub fn fetch(which: u8) -> Result<(), FetchError<u8>> {
+	match which {
+		0 => Err(Timeout)?,
+		1 => Err(NotFound)?,
+		other => Err(FetchError::Other(other)),
+	}
+}
+
+pub fn sync(which: u8) -> Result<(), SyncError<u8>> {
+	Ok(fetch(which)?)
+}
+
+fn main() {
+	for which in 0..3 {
+		if let Err(e) = sync(which) {
+			println!("Error: {}", e);
+		}
+	}
+}
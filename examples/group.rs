@@ -0,0 +1,69 @@
+use std::convert::TryFrom;
+
+use compound_error::CompoundError;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Timeout")]
+pub struct Timeout;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Connection Refused")]
+pub struct Refused;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Disk Full")]
+pub struct DiskFull;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Corrupted")]
+pub struct Corrupted;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Invalid Input")]
+pub struct InvalidInput;
+
+/// Errors of a service, grouped by subsystem.
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Service Error")]
+pub enum ServiceError {
+	#[compound_error(group = "network")]
+	Timeout(Timeout),
+	#[compound_error(group = "network")]
+	Refused(Refused),
+	#[compound_error(group = "storage")]
+	DiskFull(DiskFull),
+	#[compound_error(group = "storage")]
+	Corrupted(Corrupted),
+	InvalidInput(InvalidInput),
+}
+
+pub fn connect(which: u8) -> Result<(), ServiceErrorNetwork> {
+	if which == 0 {
+		Err(Timeout)?
+	} else {
+		Err(Refused)?
+	}
+}
+
+pub fn serve(which: u8) -> Result<(), ServiceError> {
+	match which {
+		0 | 1 => Ok(connect(which)?),
+		2 => Err(DiskFull)?,
+		_ => Err(InvalidInput)?,
+	}
+}
+
+fn main() {
+	for which in 0..4 {
+		if let Err(e) = serve(which) {
+			match e.group() {
+				Some(ServiceErrorGroup::Network) => println!("Network error: {}", e),
+				Some(ServiceErrorGroup::Storage) => {
+					let e = ServiceErrorStorage::try_from(e).unwrap();
+					println!("Storage error: {}", e);
+				},
+				None => println!("Error: {}", e),
+			}
+		}
+	}
+}
@@ -0,0 +1,49 @@
+use std::fmt;
+
+use compound_error::CompoundError;
+
+#[derive(Debug, CompoundError)]
+pub struct Foo;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(skip_display)]
+pub enum ExampleError {
+	Foo(Foo),
+	#[compound_error(ignore)]
+	OutOfRange {
+		value: u32,
+		max: u32,
+	},
+}
+
+impl fmt::Display for ExampleError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Foo(err) => write!(f, "Example error: {}", err),
+			Self::OutOfRange {
+				value,
+				max,
+			} => write!(f, "Value {} is larger than {}", value, max),
+		}
+	}
+}
+
+pub fn throws_example(value: u32) -> Result<(), ExampleError> {
+	if value > 10 {
+		Err(ExampleError::OutOfRange {
+			value,
+			max: 10,
+		})
+	} else {
+		Err(Foo)?
+	}
+}
+
+fn main() {
+	if let Err(e) = throws_example(5) {
+		println!("Error: {}", e);
+	}
+	if let Err(e) = throws_example(42) {
+		println!("Error: {}", e);
+	}
+}
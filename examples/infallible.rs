@@ -0,0 +1,33 @@
+use std::convert::Infallible;
+
+use compound_error::CompoundError;
+
+#[derive(Debug, CompoundError)]
+pub struct Foo;
+
+#[derive(Debug, CompoundError)]
+pub enum ExampleError {
+	Foo(Foo),
+	#[compound_error(infallible)]
+	Never(Infallible),
+}
+
+pub fn never_fails() -> Result<u8, Infallible> {
+	Ok(42)
+}
+
+pub fn throws_example(fail: bool) -> Result<u8, ExampleError> {
+	let value = never_fails()?;
+
+	if fail {
+		Err(Foo)?
+	} else {
+		Ok(value)
+	}
+}
+
+fn main() {
+	if let Err(e) = throws_example(true) {
+		println!("Error: {}", e);
+	}
+}
@@ -0,0 +1,103 @@
+use compound_error::CompoundError;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Not Found")]
+pub struct NotFound;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Timeout")]
+pub struct Timeout;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Refused")]
+pub struct Refused;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Request Error")]
+pub enum RequestError {
+	NotFound(NotFound),
+	Timeout(Timeout),
+	Refused(Refused),
+}
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Connection Failed")]
+pub struct ConnectionFailed(pub &'static str);
+
+/// Merges timeouts and refused connections into a single variant.
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Sync Error")]
+pub enum SyncError {
+	#[compound_error(inline_from(RequestError, with = from_request))]
+	NotFound(NotFound),
+	Connection(ConnectionFailed),
+}
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Unsupported")]
+pub struct Unsupported;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Fetch Error")]
+#[non_exhaustive]
+pub enum FetchError {
+	NotFound(NotFound),
+	Timeout(Timeout),
+	Refused(Refused),
+	Unsupported(Unsupported),
+}
+
+/// Inlines only some variants of `FetchError`, routing all others into
+/// `Other`.
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Mirror Error")]
+pub enum MirrorError {
+	#[compound_error(inline_from(FetchError, fallback = Other))]
+	Timeout(Timeout),
+	#[compound_error(inline_from(FetchError))]
+	NotFound(NotFound),
+	#[compound_error(message)]
+	Other(String),
+}
+
+fn from_request(err: RequestError) -> SyncError {
+	match err {
+		RequestError::Timeout(_) => ConnectionFailed("timed out").into(),
+		RequestError::Refused(_) => ConnectionFailed("refused").into(),
+		RequestError::NotFound(err) => err.into(),
+	}
+}
+
+pub fn request(which: u8) -> Result<(), RequestError> {
+	match which {
+		0 => Err(NotFound)?,
+		1 => Err(Timeout)?,
+		_ => Err(Refused)?,
+	}
+}
+
+pub fn sync(which: u8) -> Result<(), SyncError> {
+	Ok(request(which)?)
+}
+
+pub fn mirror(which: u8) -> Result<(), MirrorError> {
+	match which {
+		0 => Err(FetchError::from(NotFound))?,
+		1 => Err(FetchError::from(Timeout))?,
+		2 => Err(FetchError::from(Refused))?,
+		_ => Err(FetchError::from(Unsupported))?,
+	}
+}
+
+fn main() {
+	for which in 0..3 {
+		if let Err(e) = sync(which) {
+			println!("Error: {}", e);
+		}
+	}
+	for which in 0..4 {
+		if let Err(e) = mirror(which) {
+			println!("Error: {}", e);
+		}
+	}
+}
@@ -0,0 +1,72 @@
+use std::io;
+use std::io::Read;
+
+use compound_error::CompoundError;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Bad Header")]
+pub struct BadHeader;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Truncated")]
+pub struct Truncated;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Decode Error", io_kind = InvalidData, from_inline)]
+pub enum DecodeError {
+	BadHeader(BadHeader),
+	#[compound_error(io_kind = UnexpectedEof)]
+	Truncated(Truncated),
+}
+
+#[derive(Debug, CompoundError)]
+#[compound_error(
+	title = "Load Error",
+	from_cold,
+	from_io(NotFound => Missing, PermissionDenied => Forbidden, _ => Io)
+)]
+pub enum LoadError {
+	Missing(io::Error),
+	Forbidden(io::Error),
+	Io(io::Error),
+}
+
+pub fn load(path: &str) -> Result<String, LoadError> {
+	Ok(std::fs::read_to_string(path)?)
+}
+
+/// A reader adapter, which is forced to report `io::Error`s
+pub struct Decoder<'a>(&'a [u8]);
+
+impl<'a> Decoder<'a> {
+	fn decode(&mut self, buf: &mut [u8]) -> Result<usize, DecodeError> {
+		match self.0 {
+			[] => Err(Truncated)?,
+			[b'#', ..] => Err(BadHeader)?,
+			data => {
+				let len = data.len().min(buf.len());
+				buf[..len].copy_from_slice(&data[..len]);
+				self.0 = &data[len..];
+				Ok(len)
+			},
+		}
+	}
+}
+
+impl<'a> Read for Decoder<'a> {
+	fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+		Ok(self.decode(buf)?)
+	}
+}
+
+fn main() {
+	for input in [&b"#header"[..], &b""[..]] {
+		let err = Decoder(input).read(&mut [0; 8]).unwrap_err();
+		println!("{:?}: {}", err.kind(), err);
+	}
+
+	if let Err(err) = load("/does/not/exist") {
+		println!("{}", err);
+		assert!(matches!(err, LoadError::Missing(_)));
+	}
+}
@@ -0,0 +1,56 @@
+use compound_error::fluent::FluentBundle;
+use compound_error::fluent::FluentResource;
+use compound_error::CompoundError;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Timeout", msg_key = "errors.db.timeout")]
+pub struct Timeout;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Constraint Violated")]
+pub struct ConstraintViolated;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Database Error")]
+pub enum DbError {
+	#[compound_error(msg_key = "errors.db.timeout")]
+	Timeout(Timeout),
+	ConstraintViolated(ConstraintViolated),
+}
+
+fn bundle(locale: &str, source: &str) -> FluentBundle<FluentResource> {
+	let resource = FluentResource::try_new(source.to_string()).expect("valid resource");
+	let mut bundle = FluentBundle::new(vec![locale.parse().expect("valid locale")]);
+	bundle.set_use_isolating(false);
+	bundle
+		.add_resource(resource)
+		.expect("no conflicting messages");
+	bundle
+}
+
+fn main() {
+	let bundles = [
+		bundle(
+			"en-US",
+			"errors-db-timeout = The database did not answer in \
+			 time.\ndb_error-constraint_violated = The data is inconsistent.\n",
+		),
+		bundle(
+			"de-DE",
+			"errors-db-timeout = Die Datenbank hat nicht rechtzeitig geantwortet.\n",
+		),
+	];
+
+	let errors = [DbError::from(Timeout), DbError::from(ConstraintViolated)];
+
+	for bundle in &bundles {
+		for err in &errors {
+			println!(
+				"{}: {}",
+				err.message_key(),
+				err.localized_message(bundle)
+					.unwrap_or_else(|| err.to_string())
+			);
+		}
+	}
+}
@@ -0,0 +1,45 @@
+use std::panic::Location;
+
+use compound_error::CompoundError;
+
+#[derive(Debug, CompoundError)]
+pub struct Foo;
+
+#[derive(Debug, CompoundError)]
+pub struct Bar;
+
+#[derive(Debug, CompoundError)]
+pub enum DerivedError {
+	#[compound_error(location)]
+	Foo(Foo, &'static Location<'static>),
+	Bar(Bar),
+}
+
+#[compound_error::compound_error]
+pub enum ExampleError {
+	#[compound_error(location)]
+	Foo(Foo),
+	#[compound_error(location)]
+	Bar(Bar),
+}
+
+pub fn throws_foo() -> Result<(), Foo> {
+	Err(Foo)
+}
+
+pub fn throws_derived() -> Result<(), DerivedError> {
+	Ok(throws_foo()?)
+}
+
+pub fn throws_example() -> Result<(), ExampleError> {
+	Ok(throws_foo()?)
+}
+
+fn main() {
+	if let Err(e) = throws_derived() {
+		println!("Error: {} at {:?}", e, e.location());
+	}
+	if let Err(e) = throws_example() {
+		println!("Error: {} at {:?}", e, e.location());
+	}
+}
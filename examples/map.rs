@@ -0,0 +1,31 @@
+use compound_error::CompoundError;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Timeout")]
+pub struct Timeout;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Job Error")]
+pub enum JobError<T: std::fmt::Debug> {
+	Timeout(Timeout),
+	#[compound_error(no_source, map)]
+	Failed(T),
+}
+
+pub fn run(code: u8) -> Result<(), JobError<u8>> {
+	match code {
+		0 => Ok(()),
+		1 => Err(Timeout)?,
+		code => Err(JobError::Failed(code)),
+	}
+}
+
+fn main() {
+	for code in 1..3 {
+		if let Err(e) = run(code) {
+			// Only the payload of `Failed` is mapped, `Timeout` is kept as is
+			let e: JobError<String> = e.map_failed(|code| format!("exit code {}", code));
+			println!("Error: {:?}", e);
+		}
+	}
+}
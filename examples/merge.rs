@@ -0,0 +1,65 @@
+use compound_error::merge_errors;
+use compound_error::CompoundError;
+
+#[derive(Debug, CompoundError)]
+pub struct Timeout;
+
+#[derive(Debug, CompoundError)]
+pub struct Io;
+
+#[derive(Debug, CompoundError)]
+pub struct DiskFull;
+
+pub mod network {
+	use compound_error::CompoundError;
+
+	use super::Io;
+	use super::Timeout;
+
+	#[derive(Debug, CompoundError)]
+	#[compound_error(mergeable)]
+	pub enum NetworkError {
+		Timeout(Timeout),
+		Io(Io),
+	}
+}
+
+use network::NetworkError;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(mergeable)]
+pub enum StorageError {
+	Io(Io),
+	Full(DiskFull),
+}
+
+// The variants are provided by the merged enums, as they are `mergeable`
+merge_errors! {
+	#[compound_error(title = "Combined Error")]
+	pub enum Combined = network::NetworkError + StorageError;
+}
+
+pub fn throws_network() -> Result<(), NetworkError> {
+	Err(Timeout)?
+}
+
+pub fn throws_storage() -> Result<(), StorageError> {
+	Err(DiskFull)?
+}
+
+pub fn throws_combined(which: u8) -> Result<(), Combined> {
+	if which == 0 {
+		Ok(throws_network()?)
+	} else {
+		Ok(throws_storage()?)
+	}
+}
+
+fn main() {
+	if let Err(e) = throws_combined(0) {
+		println!("Error: {}", e);
+	}
+	if let Err(e) = throws_combined(1) {
+		println!("Error: {}", e);
+	}
+}
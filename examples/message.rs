@@ -0,0 +1,49 @@
+use std::borrow::Cow;
+
+use compound_error::CompoundError;
+
+#[derive(Debug, CompoundError)]
+pub struct Foo;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Config Error")]
+pub enum ConfigError {
+	Foo(Foo),
+	#[compound_error(message)]
+	Message(String),
+}
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Parse Error")]
+pub enum ParseError {
+	#[compound_error(message)]
+	Message(Cow<'static, str>),
+}
+
+pub fn check_port(port: u32) -> Result<u16, ConfigError> {
+	if port > u32::from(u16::MAX) {
+		Err(format!("port {} is out of range", port))?
+	}
+	if port == 0 {
+		Err("port must not be zero")?
+	}
+	if port == 1 {
+		Err(Foo)?
+	}
+	Ok(port as u16)
+}
+
+pub fn parse(input: &str) -> Result<u8, ParseError> {
+	input.parse().map_err(|_| ParseError::from("not a number"))
+}
+
+fn main() {
+	for port in [0, 1, 70000] {
+		if let Err(e) = check_port(port) {
+			println!("Error: {}", e);
+		}
+	}
+	if let Err(e) = parse("x") {
+		println!("Error: {}", e);
+	}
+}
@@ -0,0 +1,58 @@
+use std::collections::HashMap;
+
+use compound_error::CompoundError;
+
+#[derive(Debug, CompoundError)]
+pub struct Foo;
+
+#[derive(Debug, CompoundError)]
+pub struct Bar;
+
+#[derive(Debug)]
+pub struct RequestInfo {
+	pub request_id: u64,
+}
+
+#[derive(Debug, CompoundError)]
+#[compound_error(meta = RequestInfo)]
+pub enum ExampleError {
+	Foo(Foo),
+	Bar(Bar),
+}
+
+#[derive(Debug, CompoundError)]
+#[compound_error(meta(HashMap<String, u64>, default))]
+pub enum GenericError<T: std::fmt::Debug>
+where
+	T: 'static,
+{
+	Foo(Foo),
+	#[compound_error(no_source)]
+	Other(T),
+}
+
+pub fn throws_example() -> Result<(), ExampleError> {
+	Err(Foo)?
+}
+
+pub fn handle_request(request_id: u64) -> Result<(), ExampleErrorWithMeta> {
+	throws_example().map_err(|err| {
+		err.with_meta(RequestInfo {
+			request_id,
+		})
+	})
+}
+
+pub fn handle_generic() -> Result<(), GenericErrorWithMeta<u8>> {
+	Err(GenericError::Other(42))?
+}
+
+fn main() {
+	if let Err(e) = handle_request(42) {
+		println!("Error: {} (request {})", e, e.meta.request_id);
+	}
+	if let Err(mut e) = handle_generic() {
+		e.meta.insert("retries".to_string(), 3);
+		println!("Error: {} ({:?})", e, e.meta);
+	}
+}
@@ -0,0 +1,32 @@
+use std::error::Error;
+
+use compound_error::CompoundError;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Disk Full")]
+pub struct DiskFull;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Save Error")]
+pub enum SaveError {
+	/// The write failed, possibly with a known cause
+	#[compound_error(optional)]
+	WriteFailed(Option<DiskFull>),
+}
+
+pub fn save(known_cause: bool) -> Result<(), SaveError> {
+	if known_cause {
+		Err(DiskFull)?
+	} else {
+		Err(SaveError::WriteFailed(None))
+	}
+}
+
+fn main() {
+	for known_cause in [true, false] {
+		if let Err(e) = save(known_cause) {
+			println!("Error: {}", e);
+			println!("Source: {:?}", e.source().map(|source| source.to_string()));
+		}
+	}
+}
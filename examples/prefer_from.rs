@@ -0,0 +1,29 @@
+use std::io;
+use std::io::Read;
+use std::io::Write;
+
+use compound_error::CompoundError;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Copy Error")]
+pub enum CopyError {
+	/// Failed to read the input
+	#[compound_error(prefer_from)]
+	ReadFailed(io::Error),
+	/// Failed to write the output
+	WriteFailed(io::Error),
+}
+
+pub fn copy(mut input: impl Read, mut output: impl Write) -> Result<usize, CopyError> {
+	let mut buffer = Vec::new();
+	input.read_to_end(&mut buffer)?;
+	output.write_all(&buffer).map_err(CopyError::WriteFailed)?;
+	Ok(buffer.len())
+}
+
+fn main() {
+	let mut output = [0u8; 4];
+	if let Err(e) = copy(&b"too long"[..], &mut output[..]) {
+		println!("Error: {}", e);
+	}
+}
@@ -0,0 +1,47 @@
+use compound_error::CompoundError;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Unexpected Token")]
+pub struct UnexpectedToken;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Unbalanced Parentheses")]
+pub struct Unbalanced;
+
+/// Errors of a parser for nested expressions, where errors within a group are
+/// wrapped into `InGroup`.
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Parse Error")]
+pub enum ParseError {
+	Token(UnexpectedToken),
+	Unbalanced(Unbalanced),
+	InGroup(Box<ParseError>),
+}
+
+pub fn parse(input: &str) -> Result<(), ParseError> {
+	match input.strip_prefix('(') {
+		Some(rest) => {
+			let inner = rest.strip_suffix(')').ok_or(Unbalanced)?;
+			parse(inner).map_err(|err| ParseError::InGroup(Box::new(err)))
+		},
+		None if input.chars().all(char::is_alphanumeric) => Ok(()),
+		None => Err(UnexpectedToken)?,
+	}
+}
+
+fn main() {
+	for input in &["(foo)", "((+))", "((foo)"] {
+		match parse(input) {
+			Ok(()) => println!("Parsed: {}", input),
+			Err(e) => {
+				println!("Error: {}", e);
+
+				let mut source = std::error::Error::source(&e);
+				while let Some(cause) = source {
+					println!("  caused by: {}", cause);
+					source = cause.source();
+				}
+			},
+		}
+	}
+}
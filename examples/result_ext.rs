@@ -0,0 +1,44 @@
+use compound_error::CompoundError;
+
+#[derive(Debug, CompoundError)]
+pub struct Foo;
+
+#[derive(Debug, CompoundError)]
+pub struct Bar;
+
+#[derive(Debug, CompoundError)]
+pub struct ParseError;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(result_ext)]
+pub enum ExampleError {
+	Foo(Foo),
+	Bar(Bar),
+	#[compound_error(no_source)]
+	Message(String),
+}
+
+pub fn throws_foo() -> Result<(), Foo> {
+	Err(Foo)
+}
+
+pub fn throws_message() -> Result<(), &'static str> {
+	Err("something went wrong")
+}
+
+pub fn throws_example(which: u8) -> Result<(), ExampleError> {
+	if which == 0 {
+		throws_foo().into_example_error()
+	} else {
+		throws_message().or_message()
+	}
+}
+
+fn main() {
+	if let Err(e) = throws_example(0) {
+		println!("Error: {}", e);
+	}
+	if let Err(e) = throws_example(1) {
+		println!("Error: {:?}", e);
+	}
+}
@@ -0,0 +1,59 @@
+use compound_error::CompoundError;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(
+	title = "Timeout",
+	description = "the peer did not answer",
+	code = "E0001"
+)]
+pub struct Timeout;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Disk Full", skip_error, severity = "critical")]
+pub struct DiskFull;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(
+	title = "Sync Error",
+	code = "E0100",
+	severity = "warning",
+	category = "sync",
+	report_fields,
+	url_base = "https://docs.example.com/errors/",
+	display_url
+)]
+pub enum SyncError {
+	#[compound_error(code = "E0101")]
+	Timeout(Timeout),
+	#[compound_error(no_source, severity = "critical", category = "storage")]
+	DiskFull(DiskFull),
+}
+
+/// Reports any compound error in a uniform way.
+fn report(err: &dyn CompoundError) {
+	println!(
+		"{} ({}): [{}] {}{}{}",
+		err.severity(),
+		err.category(),
+		err.code().unwrap_or("-"),
+		err.title(),
+		err.variant_name()
+			.map(|name| format!("::{}", name))
+			.unwrap_or_default(),
+		err.description()
+			.map(|desc| format!(" ({})", desc))
+			.unwrap_or_default(),
+	);
+}
+
+fn main() {
+	report(&Timeout);
+	report(&SyncError::from(Timeout));
+	report(&SyncError::from(DiskFull));
+
+	println!("{}", SyncError::from(Timeout));
+
+	for (name, value) in SyncError::from(Timeout).report_fields() {
+		println!("{} = {:?}", name, value);
+	}
+}
@@ -0,0 +1,42 @@
+use std::error::Error;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use compound_error::CompoundError;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Connection Lost")]
+pub struct ConnectionLost;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Bad Request")]
+pub struct BadRequest;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Request Error")]
+pub enum RequestError {
+	/// Shared among all pending requests of a connection
+	#[compound_error(source_deref)]
+	Connection(Arc<ConnectionLost>),
+	#[compound_error(source_deref)]
+	Request(Box<BadRequest>),
+	#[compound_error(source_deref)]
+	Local(Rc<BadRequest>),
+	#[compound_error(source_deref)]
+	Other(Box<dyn Error + Send + Sync>),
+}
+
+fn main() {
+	let lost = Arc::new(ConnectionLost);
+	let errors = [
+		RequestError::from(lost.clone()),
+		RequestError::from(Box::new(BadRequest)),
+		RequestError::from(Rc::new(BadRequest)),
+		RequestError::from(Box::<dyn Error + Send + Sync>::from("timeout")),
+	];
+
+	for err in &errors {
+		println!("Error: {}", err);
+		println!("Source: {}", err.source().unwrap());
+	}
+}
@@ -0,0 +1,47 @@
+use std::convert::TryFrom;
+
+use compound_error::CompoundError;
+
+#[derive(Debug, CompoundError)]
+pub struct Io;
+
+#[derive(Debug, CompoundError)]
+pub struct Parse;
+
+#[derive(Debug, CompoundError)]
+pub struct NotFound;
+
+#[derive(Debug, CompoundError)]
+pub struct Denied;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Store Error", subset(ReadOnlyError: Io, Parse, NotFound))]
+pub enum StoreError {
+	Io(Io),
+	Parse(Parse),
+	NotFound(NotFound),
+	Denied(Denied),
+}
+
+pub fn read(which: u8) -> Result<(), ReadOnlyError> {
+	if which == 0 {
+		Err(Parse)?
+	} else {
+		Err(NotFound)?
+	}
+}
+
+pub fn write() -> Result<(), StoreError> {
+	read(0)?;
+	Err(Denied)?
+}
+
+fn main() {
+	if let Err(e) = read(1) {
+		println!("Error: {}", e);
+	}
+	if let Err(e) = write() {
+		println!("Error: {}", e);
+		println!("Read-only: {:?}", ReadOnlyError::try_from(e));
+	}
+}
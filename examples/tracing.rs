@@ -0,0 +1,54 @@
+use compound_error::CompoundError;
+
+#[derive(Debug, CompoundError)]
+pub struct Foo;
+
+#[derive(Debug, CompoundError)]
+pub struct Bar;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(trace_from)]
+pub enum CompoundFoo {
+	Foo(Foo),
+	Bar(Bar),
+}
+
+/// A status without `Display`, which is thus traced by its `Debug` output
+#[derive(Debug)]
+pub struct Status(pub u16);
+
+#[derive(Debug, CompoundError)]
+#[compound_error(trace_from = "warn")]
+pub enum ExampleError {
+	#[compound_error(inline_from(CompoundFoo))]
+	Foo(Foo),
+	#[compound_error(inline_from(CompoundFoo))]
+	Bar(Bar),
+	#[compound_error(no_source)]
+	Status(Status),
+}
+
+pub fn throws_compound_foo() -> Result<(), CompoundFoo> {
+	Err(Bar)?
+}
+
+pub fn throws_example() -> Result<(), ExampleError> {
+	Ok(throws_compound_foo()?)
+}
+
+pub fn throws_status() -> Result<(), ExampleError> {
+	Err(Status(503))?
+}
+
+fn main() {
+	tracing_subscriber::fmt()
+		.with_max_level(tracing::Level::DEBUG)
+		.init();
+
+	if let Err(e) = throws_example() {
+		println!("Error: {}", e);
+	}
+	if let Err(e) = throws_status() {
+		println!("Error: {}", e);
+	}
+}
@@ -16,6 +16,17 @@ pub enum Foo {
 #[derive(Debug, CompoundError)]
 pub struct Bar;
 
+/// Wraps any error without changing its appearance
+#[derive(Debug, CompoundError)]
+#[compound_error(transparent, deref)]
+pub struct Wrap<T: std::error::Error + 'static>(T);
+
+impl Foo1 {
+	pub fn hint(&self) -> &'static str {
+		"try again"
+	}
+}
+
 #[derive(Debug, CompoundError)]
 pub enum ExampleError {
 	#[compound_error(transparent)]
@@ -48,4 +59,7 @@ fn main() {
 	if let Err(e) = throws_compound_err(2) {
 		println!("Error: {}", e);
 	}
+
+	let wrapped = Wrap(Foo1);
+	println!("Error: {} ({})", wrapped, wrapped.hint());
 }
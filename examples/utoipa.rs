@@ -0,0 +1,40 @@
+use compound_error::CompoundError;
+use utoipa::openapi::schema::ComponentsBuilder;
+use utoipa::openapi::OpenApiBuilder;
+use utoipa::IntoResponses;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Not Found")]
+pub struct NotFound;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Bad Request")]
+pub struct BadRequest;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Database Error")]
+pub struct DatabaseError;
+
+/// Errors of the example API, documented as responses of its operations.
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "API Error", description = "the request failed", utoipa)]
+pub enum ApiError {
+	#[compound_error(code = "E404", status = 404)]
+	NotFound(NotFound),
+	#[compound_error(code = "E400", status = 400)]
+	BadRequest(BadRequest),
+	#[compound_error(code = "E500")]
+	Database(DatabaseError),
+}
+
+fn main() {
+	let components = ComponentsBuilder::new()
+		.schema_from::<ApiError>()
+		.responses_from_iter(ApiError::responses())
+		.build();
+	let api = OpenApiBuilder::new().components(Some(components)).build();
+	println!("{}", api.to_pretty_json().unwrap());
+
+	let err = ApiError::from(NotFound);
+	println!("Error: {} (HTTP {})", err, err.http_status());
+}
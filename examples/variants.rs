@@ -0,0 +1,39 @@
+use compound_error::CompoundError;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Timeout")]
+pub struct Timeout;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Refused")]
+pub struct Refused;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Connect Error", variants)]
+pub enum ConnectError {
+	Timeout(Timeout),
+	Refused(Refused),
+}
+
+pub fn connect(slow: bool) -> Result<(), ConnectError> {
+	if slow {
+		Err(Timeout)?
+	} else {
+		Err(Refused)?
+	}
+}
+
+fn main() {
+	println!(
+		"ConnectError has {} variants: {:?}",
+		ConnectError::VARIANT_COUNT,
+		ConnectError::VARIANTS
+	);
+
+	// Errors are compared by their variant only, regardless of their arguments
+	if let Err(e) = connect(true) {
+		assert!(e.same_variant(&ConnectError::Timeout(Timeout)));
+		assert!(!e.same_variant(&ConnectError::Refused(Refused)));
+		println!("Error: {}", e);
+	}
+}
@@ -0,0 +1,140 @@
+//! Variants, which are removed by a false configuration predicate as `any()`,
+//! must not break any of the generated code.
+
+use std::convert::TryFrom;
+
+use compound_error::CompoundError;
+
+#[derive(Debug, CompoundError)]
+pub struct Foo;
+
+#[derive(Debug, CompoundError)]
+pub struct Bar;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(variants, ffi, mergeable)]
+#[repr(u8)]
+pub enum Derived {
+	Foo(Foo) = 1,
+	#[cfg(any())]
+	Bar(Bar) = 2,
+}
+
+#[compound_error::compound_error(variants, ffi)]
+#[repr(u8)]
+pub enum Attribute {
+	Foo(Foo) = 1,
+	#[compound_error(cfg(any()))]
+	Bar(Bar) = 2,
+}
+
+#[test]
+fn derive_with_plain_cfg() {
+	let err = Derived::from(Foo);
+	assert_eq!(err.variant_name(), Some("Foo"));
+	assert_eq!(err.message_key(), "derived.foo");
+	assert_eq!(err.discriminant(), 1);
+	assert_eq!(err.ffi_code(), DerivedCode::Foo);
+	assert_eq!(Derived::VARIANTS, &["Foo"]);
+}
+
+#[test]
+fn attribute_with_cfg_helper() {
+	let err = Attribute::from(Foo);
+	assert_eq!(err.variant_name(), Some("Foo"));
+	assert_eq!(err.message_key(), "attribute.foo");
+	assert_eq!(err.discriminant(), 1);
+	assert_eq!(err.ffi_code(), AttributeCode::Foo);
+	assert_eq!(Attribute::VARIANTS, &["Foo"]);
+}
+
+/// Codes of variants following a removed one must not shift.
+#[compound_error::compound_error(ffi)]
+pub enum Implicit {
+	#[compound_error(cfg(any()))]
+	Bar(Bar),
+	Foo(Foo),
+}
+
+#[test]
+fn ffi_codes_are_reserved() {
+	assert_eq!(Implicit::from(Foo).ffi_code() as i32, 2);
+}
+
+#[derive(Debug, CompoundError)]
+pub struct Baz;
+
+/// Implicit discriminants following a removed variant are counted without it.
+#[compound_error::compound_error(ffi)]
+#[repr(u8)]
+pub enum Counted {
+	Foo(Foo) = 1,
+	#[compound_error(cfg(any()))]
+	Bar(Bar),
+	Baz(Baz),
+}
+
+#[test]
+fn implicit_discriminants_skip_removed_variants() {
+	let err = Counted::from(Baz);
+	assert_eq!(err.discriminant(), 2);
+	assert_eq!(err.ffi_code() as u8, 2);
+}
+
+/// Removed variants of a subset are removed from its conversions as well.
+#[compound_error::compound_error(subset(Small: Foo, Bar))]
+pub enum Subsetted {
+	Foo(Foo),
+	#[compound_error(cfg(any()))]
+	Bar(Bar),
+	Baz(Baz),
+}
+
+#[test]
+fn subsets_skip_removed_variants() {
+	assert!(Small::try_from(Subsetted::from(Foo)).is_ok());
+	assert!(Small::try_from(Subsetted::from(Baz)).is_err());
+	assert!(matches!(
+		Subsetted::from(Small::Foo(Foo)),
+		Subsetted::Foo(_)
+	));
+}
+
+/// Removed variants of a group are removed from its conversions as well.
+#[compound_error::compound_error]
+pub enum Grouped {
+	#[compound_error(group = "common")]
+	Foo(Foo),
+	#[compound_error(cfg(any()), group = "common")]
+	Bar(Bar),
+	Baz(Baz),
+}
+
+#[test]
+fn groups_skip_removed_variants() {
+	assert_eq!(Grouped::from(Foo).group(), Some(GroupedGroup::Common));
+	assert_eq!(Grouped::from(Baz).group(), None);
+	assert!(GroupedCommon::try_from(Grouped::from(Foo)).is_ok());
+	assert!(GroupedCommon::try_from(Grouped::from(Baz)).is_err());
+}
+
+/// Removed variants of mergeable enums are removed from merged enums as well.
+#[compound_error::compound_error(mergeable)]
+pub enum Mergeable {
+	Foo(Foo),
+	#[compound_error(cfg(any()))]
+	Bar(Bar),
+}
+
+compound_error::merge_errors! {
+	pub enum Merged = Mergeable + Derived;
+}
+
+#[test]
+fn merges_skip_removed_variants() {
+	assert_eq!(
+		Merged::from(Mergeable::from(Foo)).variant_name(),
+		Some("Foo")
+	);
+	assert_eq!(Merged::from(Derived::from(Foo)).variant_name(), Some("Foo"));
+}
@@ -0,0 +1,61 @@
+//! Compound errors within the shown or reported source chain must not repeat
+//! their own sources, which are part of the chain anyway.
+
+use compound_error::CompoundError;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Timeout", description = "no response")]
+pub struct Timeout;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Connect Error")]
+pub enum ConnectError {
+	Timeout(Timeout),
+}
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Sync Error", chain_depth = *)]
+pub enum SyncError {
+	Connect(ConnectError),
+}
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Job Error", chain_depth = *)]
+pub enum JobError {
+	Sync(SyncError),
+}
+
+#[test]
+fn nested_compound_errors() {
+	let err = SyncError::from(ConnectError::from(Timeout));
+	assert_eq!(
+		err.to_string(),
+		"Sync Error:\n  └ Connect Error\n    └ Timeout (no response)"
+	);
+	assert_eq!(format!("{:#}", err), "Sync Error");
+
+	let err = JobError::from(err);
+	assert_eq!(
+		err.to_string(),
+		"Job Error:\n  └ Sync Error\n    └ Connect Error\n      └ Timeout (no response)"
+	);
+}
+
+#[test]
+fn report_chain() {
+	let err = SyncError::from(ConnectError::from(Timeout));
+	let fields: Vec<_> = compound_error::report_fields(&err).collect();
+	assert_eq!(
+		fields,
+		[
+			("title", "Sync Error".to_string()),
+			("variant", "Connect".to_string()),
+			("category", "Sync Error".to_string()),
+			("severity", "error".to_string()),
+			(
+				"chain",
+				"Sync Error\nConnect Error\nTimeout (no response)".to_string()
+			),
+		]
+	);
+}
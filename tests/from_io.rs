@@ -0,0 +1,45 @@
+//! Variants targeted by `from_io` keep their own `From` impl, unless they hold
+//! an `io::Error` themselves.
+
+use std::io;
+
+use compound_error::CompoundError;
+
+/// An error wrapping an `io::Error`, which is thus no `io::Error` itself
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Wrapped")]
+pub struct Wrapped(pub io::Error);
+
+impl From<io::Error> for Wrapped {
+	fn from(err: io::Error) -> Self {
+		Wrapped(err)
+	}
+}
+
+#[derive(Debug, CompoundError)]
+#[compound_error(from_io(NotFound => Missing, _ => Wrapped))]
+pub enum LoadError {
+	Missing(io::Error),
+	Wrapped(Wrapped),
+}
+
+fn wrapped() -> Result<(), Wrapped> {
+	Err(io::Error::from(io::ErrorKind::Other))?
+}
+
+fn load() -> Result<(), LoadError> {
+	Ok(wrapped()?)
+}
+
+#[test]
+fn from_io_dispatches_on_the_kind() {
+	let err = LoadError::from(io::Error::from(io::ErrorKind::NotFound));
+	assert!(matches!(err, LoadError::Missing(_)));
+	let err = LoadError::from(io::Error::from(io::ErrorKind::Other));
+	assert!(matches!(err, LoadError::Wrapped(_)));
+}
+
+#[test]
+fn from_io_targets_keep_their_own_from() {
+	assert!(matches!(load(), Err(LoadError::Wrapped(_))));
+}
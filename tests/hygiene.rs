@@ -0,0 +1,275 @@
+//! The generated code must neither rely on the prelude nor on the names
+//! `std` and `core` referring to the respective crates, thus all of the
+//! following has to compile. Each way of generating the code is covered by its
+//! own module, whose test checks the behavior of the generated methods.
+
+#![allow(dead_code)]
+
+#[no_implicit_prelude]
+mod hygiene {
+	// Shadow the crates in relative paths
+	mod std {}
+	mod core {}
+
+	use ::compound_error::CompoundError;
+
+	#[derive(::core::fmt::Debug, CompoundError)]
+	#[compound_error(title = "Foo", description = "some foo", code = "F001", catalog)]
+	pub struct Foo;
+
+	#[derive(::core::fmt::Debug, CompoundError)]
+	#[compound_error(title = "Bar", help = "try again", url = "https://example.com/bar")]
+	pub struct Bar;
+
+	/// The plain derive
+	mod derive {
+		mod std {}
+		mod core {}
+
+		use ::compound_error::CompoundError;
+		use ::core::convert::From;
+		use ::core::convert::TryFrom;
+
+		use super::Bar;
+		use super::Foo;
+
+		#[derive(::core::fmt::Debug, CompoundError)]
+		#[compound_error(transparent, deref)]
+		pub struct Wrap(pub Foo);
+
+		#[derive(::core::fmt::Debug, CompoundError)]
+		#[compound_error(
+			title = "Inner",
+			subset(Small: Foo),
+			result_ext,
+			variants,
+			report_fields
+		)]
+		pub enum Inner {
+			Foo(Foo),
+			Bar(Bar),
+		}
+
+		#[derive(::core::fmt::Debug, CompoundError)]
+		#[compound_error(title = "Generic")]
+		#[repr(u8)]
+		pub enum Generic<T: ::std::error::Error + 'static> {
+			Foo(Foo) = 1,
+			#[compound_error(map)]
+			Other(T),
+		}
+
+		#[test]
+		fn derive() {
+			::core::assert_eq!(Foo.title(), "Foo");
+			::core::assert_eq!(Foo.description(), ::core::option::Option::Some("some foo"));
+			::core::assert_eq!(Foo.code(), ::core::option::Option::Some("F001"));
+			::core::assert_eq!(Foo::CATALOG.len(), 1);
+			::core::assert_eq!(
+				Bar.url(),
+				::core::option::Option::Some("https://example.com/bar")
+			);
+
+			let wrap = Wrap(Foo);
+			::core::assert_eq!(
+				::std::string::ToString::to_string(&wrap),
+				::std::string::ToString::to_string(&*wrap)
+			);
+
+			::core::assert_eq!(Inner::VARIANTS, &["Foo", "Bar"]);
+			::core::assert_eq!(Inner::VARIANT_COUNT, 2);
+			let err = Inner::from(Bar);
+			::core::assert_eq!(err.variant_name(), ::core::option::Option::Some("Bar"));
+			::core::assert!(err.same_variant(&Inner::Bar(Bar)));
+			::core::assert!(::core::iter::Iterator::any(
+				&mut err.report_fields(),
+				|(name, value)| { name == "title" && value == "Inner" }
+			));
+
+			::core::assert!(Small::try_from(Inner::from(Foo)).is_ok());
+			::core::assert!(Small::try_from(Inner::from(Bar)).is_err());
+			let result: ::core::result::Result<(), Foo> = ::core::result::Result::Err(Foo);
+			::core::assert!(::core::matches!(
+				InnerResultExt::into_inner(result),
+				::core::result::Result::Err(Inner::Foo(_))
+			));
+
+			let generic: Generic<Bar> = Generic::Other(Foo).map_other(|_| Bar);
+			::core::assert_eq!(generic.discriminant(), 2);
+			::core::assert_eq!(Generic::<Bar>::from(Foo).discriminant(), 1);
+		}
+	}
+
+	/// The item attribute, which additionally removes variants via `cfg`
+	mod attribute {
+		mod std {}
+		mod core {}
+
+		use ::compound_error::CompoundError;
+		use ::core::convert::From;
+
+		use super::derive::Generic;
+		use super::derive::Inner;
+		use super::derive::Small;
+		use super::derive::Wrap;
+		use super::Bar;
+		use super::Foo;
+
+		#[::compound_error::compound_error(
+			title = "Outer",
+			description = "the outer error",
+			code = "O000",
+			severity = "warning",
+			category = "test",
+			msg_key = "outer",
+			url_base = "https://example.com/",
+			display_url,
+			chain_depth = *,
+			meta = u32,
+			ffi,
+			catalog,
+			from_io(NotFound => Missing, _ => Io),
+			io_kind = Other,
+			from_cold,
+			context
+		)]
+		pub enum Outer {
+			#[compound_error(inline_from(Inner), code = "O001", severity = "info")]
+			Foo(Foo),
+			#[compound_error(inline_from(Inner), location)]
+			Bar(Bar),
+			#[compound_error(convert_source(|x| x), display_converted)]
+			Wrap(Wrap),
+			#[compound_error(transparent)]
+			Transparent(Generic<Bar>),
+			#[compound_error(no_source)]
+			Plain(u8),
+			#[compound_error(message)]
+			Message(::std::string::String),
+			#[compound_error(optional)]
+			Maybe(::core::option::Option<Small>),
+			#[compound_error(source_deref)]
+			Boxed(::std::boxed::Box<::std::io::Error>),
+			#[compound_error(infallible)]
+			Never(::core::convert::Infallible),
+			Missing(::std::io::Error),
+			Io(::std::io::Error),
+			#[compound_error(ignore)]
+			Ignored {
+				value: u8,
+			},
+			#[compound_error(cfg(any()))]
+			Removed(::std::vec::Vec<u8>),
+		}
+
+		#[test]
+		fn attribute() {
+			let err = Outer::from(Inner::from(Bar));
+			::core::assert!(err.location().is_some());
+			::core::assert_eq!(
+				err.url(),
+				::core::option::Option::Some("https://example.com/O000")
+			);
+			::core::assert_eq!(err.severity(), ::compound_error::Severity::Warning);
+			::core::assert_eq!(err.category(), "test");
+			::core::assert_eq!(err.ffi_code(), OuterCode::Bar);
+
+			let err = Outer::from(Inner::from(Foo));
+			::core::assert_eq!(err.code(), ::core::option::Option::Some("O001"));
+			::core::assert_eq!(err.severity(), ::compound_error::Severity::Info);
+			::core::assert_eq!(err.io_kind(), ::std::io::ErrorKind::Other);
+			::core::assert!(err.same_variant(&Outer::from(Foo)));
+
+			let message = ::std::string::ToString::to_string(&Outer::from("message"));
+			::core::assert!(message.contains("message"));
+
+			let err = Outer::from(::std::io::Error::from(::std::io::ErrorKind::NotFound));
+			::core::assert_eq!(err.variant_name(), ::core::option::Option::Some("Missing"));
+			let err = Outer::from(::std::io::Error::from(::std::io::ErrorKind::Other));
+			::core::assert_eq!(err.variant_name(), ::core::option::Option::Some("Io"));
+
+			let err = Outer::from(Foo).context("while testing");
+			::core::assert!(::std::string::ToString::to_string(&err).contains("while testing"));
+			::core::assert_eq!(Outer::from(Foo).with_meta(7).meta, 7);
+			::core::assert!(!::core::iter::Iterator::any(
+				&mut ::core::iter::IntoIterator::into_iter(Outer::CATALOG),
+				|entry| entry.variant == ::core::option::Option::Some("Removed")
+			));
+		}
+	}
+
+	/// The derive used via a re-export of this crate, given by `crate`
+	mod crate_override {
+		mod std {}
+		mod core {}
+
+		use ::core::convert::From;
+		use ::core::convert::TryFrom;
+
+		use super::Bar;
+		use super::Foo;
+
+		// A facade re-exporting this crate under a different name
+		mod facade {
+			pub use ::compound_error as errors;
+		}
+
+		#[derive(::core::fmt::Debug, facade::errors::CompoundError)]
+		#[compound_error(crate = "facade::errors", title = "Reexported", subset(Only: Foo))]
+		pub enum Reexported {
+			Foo(Foo),
+			Bar(Bar),
+		}
+
+		#[test]
+		fn crate_override() {
+			use facade::errors::CompoundError;
+
+			let err = Reexported::from(Bar);
+			::core::assert_eq!(err.title(), "Reexported");
+			::core::assert_eq!(err.variant_name(), ::core::option::Option::Some("Bar"));
+			::core::assert!(Only::try_from(err).is_err());
+			::core::assert!(::core::matches!(
+				Reexported::from(Only::Foo(Foo)),
+				Reexported::Foo(_)
+			));
+		}
+	}
+
+	/// The `compound_errors!` and `merge_errors!` macros
+	mod macros {
+		mod std {}
+		mod core {}
+
+		use ::compound_error::CompoundError;
+		use ::core::convert::From;
+
+		use super::derive::Inner;
+		use super::Bar;
+		use super::Foo;
+
+		::compound_error::compound_errors! {
+			#[compound_error(mergeable)]
+			pub enum Defined {
+				Foo(Foo),
+				Bar(Bar),
+			}
+		}
+
+		::compound_error::merge_errors! {
+			pub enum Merged = Defined + Inner { Foo(Foo), Bar(Bar) };
+		}
+
+		#[test]
+		fn macros() {
+			let err = Defined::from(Foo);
+			::core::assert_eq!(err.title(), "Defined");
+			::core::assert_eq!(err.variant_name(), ::core::option::Option::Some("Foo"));
+
+			let err = Merged::from(Defined::from(Bar));
+			::core::assert_eq!(err.variant_name(), ::core::option::Option::Some("Bar"));
+			let err = Merged::from(Inner::from(Foo));
+			::core::assert_eq!(err.variant_name(), ::core::option::Option::Some("Foo"));
+		}
+	}
+}
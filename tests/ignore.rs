@@ -0,0 +1,47 @@
+//! Ignored variants, which need not wrap exactly one error, must not break any
+//! of the generated code.
+
+use std::convert::TryFrom;
+
+use compound_error::CompoundError;
+
+#[derive(Debug, CompoundError)]
+pub struct Foo;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(subset(Limits: Range, Unlimited))]
+pub enum LimitError {
+	Foo(Foo),
+	#[compound_error(ignore)]
+	Range {
+		value: u32,
+	},
+	#[compound_error(ignore)]
+	Unlimited,
+}
+
+#[test]
+fn subsets_of_ignored_variants() {
+	let limits = Limits::try_from(LimitError::Range {
+		value: 42,
+	})
+	.unwrap();
+	assert!(matches!(
+		limits,
+		Limits::Range {
+			value: 42
+		}
+	));
+	assert!(matches!(
+		LimitError::from(limits),
+		LimitError::Range {
+			value: 42
+		}
+	));
+
+	assert!(matches!(
+		LimitError::from(Limits::Unlimited),
+		LimitError::Unlimited
+	));
+	assert!(Limits::try_from(LimitError::from(Foo)).is_err());
+}
@@ -0,0 +1,46 @@
+//! Metadata types are given as `meta = Type`, even with generic arguments, and
+//! only require `Default` if converting via `From` is requested.
+
+use std::collections::HashMap;
+
+use compound_error::CompoundError;
+
+#[derive(Debug, CompoundError)]
+pub struct Foo;
+
+#[derive(Debug)]
+pub struct RequestInfo {
+	pub request_id: u64,
+}
+
+#[derive(Debug, CompoundError)]
+#[compound_error(meta = RequestInfo)]
+pub enum RequestError {
+	Foo(Foo),
+}
+
+#[derive(Debug, CompoundError)]
+#[compound_error(meta = HashMap<String, u32>, title = "Tagged")]
+pub enum TaggedError {
+	Foo(Foo),
+}
+
+#[derive(Debug, CompoundError)]
+#[compound_error(meta(HashMap<String, u32>, default))]
+pub enum DefaultError {
+	Foo(Foo),
+}
+
+#[test]
+fn meta_types() {
+	let err = RequestError::from(Foo).with_meta(RequestInfo {
+		request_id: 42,
+	});
+	assert_eq!(err.meta.request_id, 42);
+
+	let err = TaggedError::from(Foo).with_meta(HashMap::new());
+	assert_eq!(err.error.title(), "Tagged");
+
+	let err = DefaultErrorWithMeta::from(DefaultError::from(Foo));
+	assert!(err.meta.is_empty());
+}
@@ -0,0 +1,42 @@
+//! `same_variant` is generated for all enums and compares their variants only,
+//! regardless of whether the arguments are comparable.
+
+use compound_error::CompoundError;
+
+/// An error without `PartialEq`
+#[derive(Debug, CompoundError)]
+pub struct Timeout {
+	pub after_ms: u64,
+}
+
+#[derive(Debug, CompoundError)]
+pub struct Refused;
+
+#[derive(Debug, CompoundError)]
+pub enum ConnectError {
+	Timeout(Timeout),
+	Refused(Refused),
+	#[compound_error(ignore)]
+	Closed {
+		code: u16,
+	},
+}
+
+#[test]
+fn same_variant_ignores_arguments() {
+	let err = ConnectError::from(Timeout {
+		after_ms: 10,
+	});
+	assert!(err.same_variant(&ConnectError::from(Timeout {
+		after_ms: 20
+	})));
+	assert!(!err.same_variant(&ConnectError::from(Refused)));
+
+	let closed = ConnectError::Closed {
+		code: 1,
+	};
+	assert!(closed.same_variant(&ConnectError::Closed {
+		code: 2
+	}));
+	assert!(!closed.same_variant(&err));
+}
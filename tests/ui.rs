@@ -0,0 +1,7 @@
+//! Diagnostics of invalid uses of the derive and the macros.
+
+#[test]
+fn ui() {
+	let t = trybuild::TestCases::new();
+	t.compile_fail("tests/ui/*.rs");
+}
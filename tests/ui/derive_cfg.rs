@@ -0,0 +1,16 @@
+use compound_error::CompoundError;
+
+#[derive(Debug, CompoundError)]
+pub struct Foo;
+
+#[derive(Debug, CompoundError)]
+pub struct Bar;
+
+#[derive(Debug, CompoundError)]
+pub enum CfgError {
+	Foo(Foo),
+	#[compound_error(cfg(any()))]
+	Bar(Bar),
+}
+
+fn main() {}
@@ -0,0 +1,24 @@
+use compound_error::CompoundError;
+
+#[derive(Debug, CompoundError)]
+pub struct Foo;
+
+#[derive(Debug)]
+pub struct Bar;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(skip_display)]
+pub enum IgnoreError {
+	#[compound_error(group = "common")]
+	Foo(Foo),
+	#[compound_error(ignore, code = "E1", group = "common")]
+	Bar(Bar),
+}
+
+impl std::fmt::Display for IgnoreError {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		write!(f, "{:?}", self)
+	}
+}
+
+fn main() {}
@@ -0,0 +1,36 @@
+use compound_error::CompoundError;
+
+#[derive(Debug, CompoundError)]
+pub struct Foo;
+
+#[derive(Debug, CompoundError)]
+pub struct Bar;
+
+#[derive(Debug, CompoundError)]
+pub struct Baz;
+
+#[derive(Debug, CompoundError)]
+pub enum Inner {
+	Foo(Foo),
+	Other(Bar),
+}
+
+/// `Inner` has no variant `Bar`
+#[derive(Debug, CompoundError)]
+pub enum MissingVariant {
+	#[compound_error(inline_from(Inner))]
+	Foo(Foo),
+	#[compound_error(inline_from(Inner))]
+	Bar(Bar),
+}
+
+/// `Inner::Other` holds a `Bar` rather than a `Baz`
+#[derive(Debug, CompoundError)]
+pub enum MismatchedPayload {
+	#[compound_error(inline_from(Inner))]
+	Foo(Foo),
+	#[compound_error(inline_from(Inner))]
+	Other(Baz),
+}
+
+fn main() {}
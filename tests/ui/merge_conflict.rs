@@ -0,0 +1,31 @@
+use compound_error::merge_errors;
+use compound_error::CompoundError;
+
+#[derive(Debug, CompoundError)]
+pub struct Timeout;
+
+#[derive(Debug, CompoundError)]
+pub struct NetworkIo;
+
+#[derive(Debug, CompoundError)]
+pub struct StorageIo;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(mergeable)]
+pub enum NetworkError {
+	Timeout(Timeout),
+	Io(NetworkIo),
+}
+
+#[derive(Debug, CompoundError)]
+#[compound_error(mergeable)]
+pub enum StorageError {
+	Io(StorageIo),
+}
+
+// Both enums have a variant `Io`, but wrapping different types
+merge_errors! {
+	pub enum Combined = NetworkError + StorageError;
+}
+
+fn main() {}
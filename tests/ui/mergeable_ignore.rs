@@ -0,0 +1,14 @@
+use compound_error::CompoundError;
+
+#[derive(Debug, CompoundError)]
+pub struct Timeout;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(mergeable)]
+pub enum NetworkError {
+	Timeout(Timeout),
+	#[compound_error(ignore)]
+	Closed,
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+use compound_error::CompoundError;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Typo", skip_dispaly)]
+pub struct Typo;
+
+fn main() {}
@@ -0,0 +1,12 @@
+use compound_error::CompoundError;
+
+#[derive(Debug, CompoundError)]
+pub struct Foo;
+
+#[derive(Debug, CompoundError)]
+pub enum TypoError {
+	#[compound_error(no_sorce)]
+	Foo(Foo),
+}
+
+fn main() {}
@@ -0,0 +1,44 @@
+//! The generated schema and responses must not require a description or codes.
+
+#![cfg(feature = "utoipa")]
+
+use compound_error::CompoundError;
+use utoipa::openapi::schema::Schema;
+use utoipa::openapi::RefOr;
+use utoipa::IntoResponses;
+use utoipa::PartialSchema;
+
+#[derive(Debug, CompoundError)]
+pub struct Timeout;
+
+#[derive(Debug, CompoundError)]
+pub struct Refused;
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Connect Error", utoipa)]
+pub enum ConnectError {
+	Timeout(Timeout),
+	#[compound_error(status = 503)]
+	Refused(Refused),
+}
+
+#[derive(Debug, CompoundError)]
+#[compound_error(title = "Plain Error", utoipa)]
+pub struct PlainError;
+
+#[test]
+fn without_description_and_codes() {
+	match ConnectError::schema() {
+		RefOr::T(Schema::Object(schema)) => {
+			assert!(schema.properties.contains_key("variant"));
+			assert!(!schema.properties.contains_key("code"));
+		},
+		_ => panic!("expected an object schema"),
+	}
+
+	let responses = ConnectError::responses();
+	assert_eq!(responses.keys().collect::<Vec<_>>(), ["500", "503"]);
+	assert_eq!(ConnectError::from(Refused).http_status(), 503);
+
+	assert_eq!(PlainError::responses().len(), 1);
+}